@@ -0,0 +1,507 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{
+    BitbucketConfig, CloudBranch, CloudPage, CloudRepository, CloudSrcEntry, Edition, ServerBranch, ServerCommit,
+    ServerPage, ServerRepository,
+};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::archive::response_stream;
+use crate::forge::client::{ArchiveStream, ForgeClient};
+use crate::forge::http::ForgeHttp;
+use crate::forge::types::{ForgeRepo, TreeEntry};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use reqwest::{Client, StatusCode, header};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub struct BitbucketClient {
+    http: ForgeHttp,
+    base_url: String, // .../2.0 on cloud, .../rest/api/1.0 on server
+    edition: Edition,
+    max_requests: usize, // server listings look up every repo's branch
+    heads: Mutex<HashMap<String, String>>, // cloud repo id -> commit the tree is read at
+}
+
+impl BitbucketClient {
+    pub fn new(config: &BitbucketConfig) -> Result<Self> {
+        use base64::{Engine as _, engine::general_purpose};
+        let credentials = general_purpose::STANDARD.encode(format!("{}:{}", config.bitbucket_username, config.app_password));
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            header::HeaderValue::from_str(&format!("Basic {}", credentials))?, // app password or server access token
+        );
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static("tele-stats"),
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self {
+            http: ForgeHttp::new(client, config.settings.max_retries),
+            base_url: config.api_root().to_string(),
+            edition: config.edition,
+            max_requests: config.settings.max_requests.max(1),
+            heads: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str, context: &str) -> Result<T> {
+        let response = self.http.get(url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), context));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Follows `next` links until the listing runs out.
+    async fn cloud_pages<T: DeserializeOwned>(&self, url: String, context: &str) -> Result<Vec<T>> {
+        let mut all_values = Vec::new();
+        let mut next = Some(url);
+
+        while let Some(url) = next {
+            let page: CloudPage<T> = self.get_json(&url, context).await?;
+            all_values.extend(page.values);
+            next = page.next;
+        }
+
+        Ok(all_values)
+    }
+
+    /// Walks `start`/`nextPageStart` until `isLastPage`.
+    async fn server_pages<T: DeserializeOwned>(&self, url: &str, context: &str) -> Result<Vec<T>> {
+        let mut all_values = Vec::new();
+        let mut start = 0;
+        let separator = if url.contains('?') { '&' } else { '?' };
+
+        loop {
+            let page_url = format!("{}{}start={}&limit=1000", url, separator, start);
+            let page: ServerPage<T> = self.get_json(&page_url, context).await?;
+            all_values.extend(page.values);
+
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next,
+                _ => break,
+            }
+        }
+
+        Ok(all_values)
+    }
+
+    fn server_repo_url(&self, id: &str) -> String {
+        let (project, slug) = id.split_once('/').unwrap_or(("", id));
+        format!("{}/projects/{}/repos/{}", self.base_url, project, slug)
+    }
+
+    pub async fn get_cloud_repos(&self, workspace: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        let url = format!("{}/repositories/{}?pagelen=100", self.base_url, workspace);
+        let repos: Vec<CloudRepository> = self.cloud_pages(url, "failed to fetch repositories").await?;
+
+        Ok(repos
+            .into_iter()
+            .filter(|repo| include_private || !repo.is_private)
+            .map(ForgeRepo::from)
+            .collect())
+    }
+
+    pub async fn get_server_repos(&self, project: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        let url = format!("{}/projects/{}/repos", self.base_url, project);
+        let repos: Vec<ServerRepository> = self.server_pages(&url, "failed to fetch repositories").await?;
+
+        // the listing has neither the default branch nor any date, look
+        // them up for max_requests repos at a time
+        let all_repos = stream::iter(repos.into_iter().filter(|repo| include_private || repo.public))
+            .map(|repo| async move {
+                let id = format!("{}/{}", repo.project.key, repo.slug);
+                let url = repo.clone_url();
+                let (default_branch, last_activity) = match self.get_server_branch_info(&id).await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        // "" reads the default branch, the analyzer still gets a go at it
+                        log::warn!("failed to resolve default branch of {}: {}", id, e);
+                        (Some(String::new()), String::new())
+                    }
+                };
+
+                ForgeRepo {
+                    id,
+                    name: repo.name,
+                    default_branch,
+                    private: !repo.public,
+                    last_activity,
+                    url,
+                    remote: None,
+                }
+            })
+            .buffered(self.max_requests)
+            .collect()
+            .await;
+
+        Ok(all_repos)
+    }
+
+    /// Default branch, None for empty repos, and the date of its head.
+    async fn get_server_branch_info(&self, id: &str) -> Result<(Option<String>, String)> {
+        let Some(branch) = self.get_server_default_branch(id).await? else {
+            return Ok((None, String::new()));
+        };
+        let last_activity = self.get_server_head(id, &branch).await?
+            .and_then(|commit| chrono::DateTime::from_timestamp_millis(commit.committer_timestamp))
+            .map(|date| date.to_rfc3339())
+            .unwrap_or_default();
+        Ok((Some(branch), last_activity))
+    }
+
+    async fn get_server_default_branch(&self, id: &str) -> Result<Option<String>> {
+        let url = format!("{}/default-branch", self.server_repo_url(id));
+        let response = self.http.get(&url).await?;
+
+        match response.status() {
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => return Ok(None), // empty repo
+            status if !status.is_success() => return Err(TeleStatsError::from_status(status, "failed to fetch default branch")),
+            _ => {}
+        }
+
+        let branch: ServerBranch = response.json().await?;
+        Ok(Some(branch.display_id))
+    }
+
+    async fn get_server_head(&self, id: &str, branch: &str) -> Result<Option<ServerCommit>> {
+        let url = with_ref(format!("{}/commits?limit=1", self.server_repo_url(id)), "until", branch);
+        let page: ServerPage<ServerCommit> = self.get_json(&url, "failed to fetch branch").await?;
+        Ok(page.values.into_iter().next())
+    }
+
+    pub async fn get_branch_head(&self, id: &str, branch: &str) -> Result<String> {
+        match self.edition {
+            Edition::Cloud => {
+                let url = format!("{}/repositories/{}/refs/branches/{}", self.base_url, id, urlencoding::encode(branch));
+                let branch: CloudBranch = self.get_json(&url, "failed to fetch branch").await?;
+                self.heads.lock().unwrap().insert(id.to_string(), branch.target.hash.clone());
+                Ok(branch.target.hash)
+            }
+            Edition::Server => self.get_server_head(id, branch).await?
+                .map(|commit| commit.id)
+                .ok_or_else(|| TeleStatsError::NotFound(format!("branch {} has no commits", branch))),
+        }
+    }
+
+    /// Commit a cloud repo is read at: the head `head_commit` saw, so tree
+    /// and files come from the same snapshot, or the branch's current one.
+    async fn cloud_commit(&self, id: &str, branch: &str) -> Result<String> {
+        let known = self.heads.lock().unwrap().get(id).cloned();
+        match known {
+            Some(hash) => Ok(hash),
+            None => self.get_branch_head(id, branch).await,
+        }
+    }
+
+    /// File paths of the branch. Neither API exposes blob shas, so every
+    /// changed repo is recounted as a whole.
+    pub async fn get_repo_files(&self, id: &str, branch: &str) -> Result<Vec<String>> {
+        match self.edition {
+            Edition::Cloud => {
+                let commit = self.cloud_commit(id, branch).await?;
+                let mut files = Vec::new();
+                let mut directories = vec![String::new()];
+
+                while let Some(directory) = directories.pop() {
+                    let url = format!(
+                        "{}/repositories/{}/src/{}/{}?pagelen=100",
+                        self.base_url, id, commit, encode_path(&directory)
+                    );
+                    let entries: Vec<CloudSrcEntry> = self.cloud_pages(url, "failed to fetch tree").await?;
+
+                    for entry in entries {
+                        match entry.entry_type.as_str() {
+                            "commit_directory" => directories.push(format!("{}/", entry.path)),
+                            "commit_file" => files.push(entry.path),
+                            _ => {} // submodules and links
+                        }
+                    }
+                }
+
+                Ok(files)
+            }
+            Edition::Server => {
+                let url = with_ref(format!("{}/files", self.server_repo_url(id)), "at", branch);
+                self.server_pages(&url, "failed to fetch tree").await
+            }
+        }
+    }
+
+    pub async fn get_file_content(&self, id: &str, file_path: &str, branch: &str) -> Result<String> {
+        let url = match self.edition {
+            Edition::Cloud => format!(
+                "{}/repositories/{}/src/{}/{}",
+                self.base_url, id, self.cloud_commit(id, branch).await?, encode_path(file_path)
+            ),
+            Edition::Server => with_ref(format!("{}/raw/{}", self.server_repo_url(id), encode_path(file_path)), "at", branch),
+        };
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch file"));
+        }
+
+        let bytes = response.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    pub async fn get_archive(&self, id: &str, branch: &str) -> Result<ArchiveStream> {
+        if self.edition == Edition::Cloud {
+            return Err(TeleStatsError::Unsupported("bitbucket cloud has no archive api, use analysis_mode: api".to_string()));
+        }
+
+        let slug = id.rsplit('/').next().unwrap_or(id);
+        let url = format!(
+            "{}/archive?format=tar.gz&prefix={}/",
+            self.server_repo_url(id), slug // top dir is skipped when counting
+        );
+        let url = with_ref(url, "at", branch);
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch archive"));
+        }
+
+        Ok(response_stream(response))
+    }
+}
+
+/// Adds the `at`/`until` parameter for `branch`, "" leaves it to the
+/// server's default branch.
+fn with_ref(url: String, name: &str, branch: &str) -> String {
+    if branch.is_empty() {
+        return url;
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}={}", url, separator, name, urlencoding::encode(branch))
+}
+
+/// Percent-encodes every segment of a repo path, keeping the slashes.
+fn encode_path(path: &str) -> String {
+    path.split('/').map(urlencoding::encode).collect::<Vec<_>>().join("/")
+}
+
+#[async_trait]
+impl ForgeClient for BitbucketClient {
+    async fn list_repos(&self, workspace: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        match self.edition {
+            Edition::Cloud => self.get_cloud_repos(workspace, include_private).await,
+            Edition::Server => self.get_server_repos(workspace, include_private).await,
+        }
+    }
+
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String> {
+        self.get_branch_head(&repo.id, branch).await
+    }
+
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>> {
+        let files = self.get_repo_files(&repo.id, branch).await?;
+        Ok(files
+            .into_iter()
+            .map(|path| TreeEntry { path, sha: String::new() })
+            .collect())
+    }
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String> {
+        self.get_file_content(&repo.id, path, branch).await
+    }
+
+    async fn fetch_archive(&self, repo: &ForgeRepo, branch: &str) -> Result<ArchiveStream> {
+        self.get_archive(&repo.id, branch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Answers `(target, status, body)` routes, anything else is a 404.
+    /// `{base}` in a body is replaced by the server's own url.
+    async fn mock(routes: &[(&str, u16, &str)]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let routes: Vec<(String, u16, String)> = routes
+            .iter()
+            .map(|(target, status, body)| (target.to_string(), *status, body.replace("{base}", &base)))
+            .collect();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 8192];
+                let read = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..read]).to_string();
+                let target = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _, _)| *route == target)
+                    .map(|(_, status, body)| (*status, body.clone()))
+                    .unwrap_or((404, String::new()));
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        base
+    }
+
+    fn client(base_url: &str, edition: Edition) -> BitbucketClient {
+        BitbucketClient {
+            http: ForgeHttp::new(Client::new(), 0),
+            base_url: base_url.to_string(),
+            edition,
+            max_requests: 4,
+            heads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn cloud_listing_follows_next() {
+        let base = mock(&[
+            (
+                "/repositories/ws?pagelen=100",
+                200,
+                r#"{"values": [{"name": "one", "full_name": "ws/one", "is_private": false, "mainbranch": {"name": "main"}, "updated_on": "2025-01-02T00:00:00+00:00"}],
+                    "next": "{base}/repositories/ws?pagelen=100&page=2"}"#,
+            ),
+            (
+                "/repositories/ws?pagelen=100&page=2",
+                200,
+                r#"{"values": [{"name": "empty", "full_name": "ws/empty", "is_private": true, "mainbranch": null, "updated_on": "2025-01-01T00:00:00+00:00"}]}"#,
+            ),
+        ]).await;
+
+        let repos = client(&base, Edition::Cloud).list_repos("ws", true).await.unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(repos[0].default_branch.as_deref(), Some("main"));
+        assert_eq!(repos[1].default_branch, None);
+
+        let public = client(&base, Edition::Cloud).list_repos("ws", false).await.unwrap();
+        assert_eq!(public.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn server_listing_walks_pages_and_survives_failed_lookups() {
+        let base = mock(&[
+            (
+                "/projects/PRJ/repos?start=0&limit=1000",
+                200,
+                r#"{"values": [{"slug": "app", "name": "App", "project": {"key": "PRJ"}, "public": true}],
+                    "isLastPage": false, "nextPageStart": 1}"#,
+            ),
+            (
+                "/projects/PRJ/repos?start=1&limit=1000",
+                200,
+                r#"{"values": [{"slug": "empty", "name": "Empty", "project": {"key": "PRJ"}, "public": true},
+                               {"slug": "broken", "name": "Broken", "project": {"key": "PRJ"}, "public": true}],
+                    "isLastPage": true}"#,
+            ),
+            ("/projects/PRJ/repos/app/default-branch", 200, r#"{"displayId": "main"}"#),
+            (
+                "/projects/PRJ/repos/app/commits?limit=1&until=main",
+                200,
+                r#"{"values": [{"id": "abc", "committerTimestamp": 1735689600000}], "isLastPage": true}"#,
+            ),
+            ("/projects/PRJ/repos/empty/default-branch", 204, ""),
+            ("/projects/PRJ/repos/broken/default-branch", 500, ""),
+        ]).await;
+
+        let repos = client(&base, Edition::Server).list_repos("PRJ", true).await.unwrap();
+        let names: Vec<&str> = repos.iter().map(|repo| repo.name.as_str()).collect();
+        assert_eq!(names, ["App", "Empty", "Broken"]);
+        assert_eq!(repos[0].id, "PRJ/app");
+        assert_eq!(repos[0].default_branch.as_deref(), Some("main"));
+        assert!(repos[0].last_activity.starts_with("2025-01-01"));
+        assert_eq!(repos[1].default_branch, None);
+        assert_eq!(repos[2].default_branch.as_deref(), Some("")); // left to the server
+    }
+
+    #[tokio::test]
+    async fn default_branch_statuses() {
+        let base = mock(&[
+            ("/projects/P/repos/none/default-branch", 204, ""),
+            ("/projects/P/repos/gone/default-branch", 404, ""),
+            ("/projects/P/repos/denied/default-branch", 401, ""),
+            ("/projects/P/repos/ok/default-branch", 200, r#"{"displayId": "develop"}"#),
+        ]).await;
+        let client = client(&base, Edition::Server);
+
+        assert_eq!(client.get_server_default_branch("P/none").await.unwrap(), None);
+        assert_eq!(client.get_server_default_branch("P/gone").await.unwrap(), None);
+        assert!(matches!(client.get_server_default_branch("P/denied").await, Err(TeleStatsError::Auth(_))));
+        assert_eq!(client.get_server_default_branch("P/ok").await.unwrap().as_deref(), Some("develop"));
+    }
+
+    #[tokio::test]
+    async fn cloud_files_are_read_at_the_head_commit() {
+        let base = mock(&[
+            ("/repositories/ws/app/refs/branches/feature%2Fx", 200, r#"{"target": {"hash": "c0ffee"}}"#),
+            (
+                "/repositories/ws/app/src/c0ffee/?pagelen=100",
+                200,
+                r#"{"values": [{"path": "my dir", "type": "commit_directory"}, {"path": "README.md", "type": "commit_file"}],
+                    "next": "{base}/repositories/ws/app/src/c0ffee/?pagelen=100&page=2"}"#,
+            ),
+            (
+                "/repositories/ws/app/src/c0ffee/?pagelen=100&page=2",
+                200,
+                r#"{"values": [{"path": "lib", "type": "commit_submodule"}]}"#,
+            ),
+            (
+                "/repositories/ws/app/src/c0ffee/my%20dir/?pagelen=100",
+                200,
+                r#"{"values": [{"path": "my dir/main.rs", "type": "commit_file"}]}"#,
+            ),
+            ("/repositories/ws/app/src/c0ffee/my%20dir/main.rs", 200, "fn main() {}"),
+        ]).await;
+        let client = client(&base, Edition::Cloud);
+
+        let mut files = client.get_repo_files("ws/app", "feature/x").await.unwrap();
+        files.sort();
+        assert_eq!(files, ["README.md", "my dir/main.rs"]);
+        let content = client.get_file_content("ws/app", "my dir/main.rs", "feature/x").await.unwrap();
+        assert_eq!(content, "fn main() {}");
+    }
+
+    #[tokio::test]
+    async fn server_files_walk_pages() {
+        let base = mock(&[
+            (
+                "/projects/P/repos/app/files?at=release%2F1.0&start=0&limit=1000",
+                200,
+                r#"{"values": ["src/main.rs"], "isLastPage": false, "nextPageStart": 1}"#,
+            ),
+            (
+                "/projects/P/repos/app/files?at=release%2F1.0&start=1&limit=1000",
+                200,
+                r#"{"values": ["Cargo.toml"], "isLastPage": true}"#,
+            ),
+            ("/projects/P/repos/app/files?start=0&limit=1000", 200, r#"{"values": ["default.rs"], "isLastPage": true}"#),
+        ]).await;
+        let client = client(&base, Edition::Server);
+
+        assert_eq!(client.get_repo_files("P/app", "release/1.0").await.unwrap(), ["src/main.rs", "Cargo.toml"]);
+        assert_eq!(client.get_repo_files("P/app", "").await.unwrap(), ["default.rs"]);
+    }
+}
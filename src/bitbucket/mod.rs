@@ -0,0 +1,43 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+
+use crate::error::Result;
+use crate::forge::formatter::ProfileLink;
+use crate::forge::service::{ForgeBackend, ForgeService};
+use crate::forge::types::{ForgeSettings};
+use client::BitbucketClient;
+use types::BitbucketConfig;
+
+pub type BitbucketService = ForgeService<BitbucketClient>;
+
+impl ForgeBackend for BitbucketClient {
+    type Config = BitbucketConfig;
+
+    const NAME: &'static str = "bitbucket";
+
+    fn connect(config: &BitbucketConfig) -> Result<Self> {
+        BitbucketClient::new(config)
+    }
+
+    fn settings(config: &BitbucketConfig) -> &ForgeSettings {
+        &config.settings
+    }
+
+    fn account(config: &BitbucketConfig) -> String {
+        config.workspace()
+    }
+
+    fn display_name(config: &BitbucketConfig) -> String {
+        config.bitbucket_username.clone()
+    }
+
+    fn profile_link(config: &BitbucketConfig) -> ProfileLink<'_> {
+        ProfileLink {
+            forge_name: "Bitbucket",
+            url: config.profile_url(),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::forge::types::{ForgeRepo, ForgeSettings};
+use serde::{Deserialize, Serialize};
+
+/// Which REST API `api_url` points at.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Edition {
+    /// bitbucket.org, API 2.0.
+    #[default]
+    Cloud,
+    /// Self-hosted Bitbucket Server/Data Center, API 1.0.
+    Server,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BitbucketConfig {
+    pub bitbucket_username: String,
+    pub app_password: String,
+    /// Cloud workspace or Server project key, "" = the user's own repos.
+    #[serde(default)]
+    pub workspace: String,
+    #[serde(default)]
+    pub edition: Edition,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default = "default_web_url")]
+    pub web_url: String,
+    #[serde(flatten)]
+    pub settings: ForgeSettings,
+}
+
+fn default_api_url() -> String {
+    "https://api.bitbucket.org/2.0".to_string()
+}
+
+fn default_web_url() -> String {
+    "https://bitbucket.org".to_string()
+}
+
+impl BitbucketConfig {
+    pub fn api_root(&self) -> &str {
+        self.api_url.trim_end_matches('/')
+    }
+
+    pub fn web_root(&self) -> &str {
+        self.web_url.trim_end_matches('/')
+    }
+
+    /// Workspace slug on Cloud, project key (`~user` for personal repos) on Server.
+    pub fn workspace(&self) -> String {
+        match (self.workspace.is_empty(), self.edition) {
+            (false, _) => self.workspace.clone(),
+            (true, Edition::Cloud) => self.bitbucket_username.clone(),
+            (true, Edition::Server) => format!("~{}", self.bitbucket_username),
+        }
+    }
+
+    pub fn profile_url(&self) -> String {
+        let workspace = self.workspace();
+        match self.edition {
+            Edition::Cloud => format!("{}/{}", self.web_root(), workspace),
+            Edition::Server => match workspace.strip_prefix('~') {
+                Some(user) => format!("{}/users/{}", self.web_root(), user),
+                None => format!("{}/projects/{}", self.web_root(), workspace),
+            },
+        }
+    }
+}
+
+/// One page of a Cloud listing, the rest is behind `next`.
+#[derive(Debug, Deserialize)]
+pub struct CloudPage<T> {
+    pub values: Vec<T>,
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+/// One page of a Server listing.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerPage<T> {
+    pub values: Vec<T>,
+    #[serde(default = "default_last_page")]
+    pub is_last_page: bool,
+    #[serde(default)]
+    pub next_page_start: Option<u64>,
+}
+
+fn default_last_page() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MainBranch {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloudRepository {
+    pub name: String,
+    pub full_name: String, // workspace/slug
+    pub is_private: bool,
+    #[serde(default)]
+    pub mainbranch: Option<MainBranch>, // null for empty repos
+    pub updated_on: String,
+    #[serde(default)]
+    pub links: CloudLinks,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CloudLinks {
+    #[serde(default)]
+    pub html: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Link {
+    pub href: String,
+    #[serde(default)]
+    pub name: String, // protocol of a Server clone link, "http" or "ssh"
+}
+
+impl From<CloudRepository> for ForgeRepo {
+    fn from(repo: CloudRepository) -> Self {
+        ForgeRepo {
+            id: repo.full_name,
+            name: repo.name,
+            default_branch: repo.mainbranch.map(|branch| branch.name),
+            private: repo.is_private,
+            last_activity: repo.updated_on,
+            url: repo.links.html.map(|link| link.href),
+            remote: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServerProject {
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServerRepository {
+    pub slug: String,
+    pub name: String,
+    pub project: ServerProject,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub links: ServerLinks,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ServerLinks {
+    #[serde(default)]
+    pub clone: Vec<Link>,
+}
+
+impl ServerRepository {
+    /// The http clone url, what a mirror of the repo would point at.
+    pub fn clone_url(&self) -> Option<String> {
+        self.links.clone.iter().find(|link| link.name == "http").map(|link| link.href.clone())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerBranch {
+    pub display_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCommit {
+    pub id: String,
+    #[serde(default)]
+    pub committer_timestamp: i64, // ms since epoch
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloudSrcEntry {
+    pub path: String,
+    #[serde(rename = "type")]
+    pub entry_type: String, // commit_file or commit_directory
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloudBranchTarget {
+    pub hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloudBranch {
+    pub target: CloudBranchTarget,
+}
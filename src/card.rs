@@ -0,0 +1,332 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::chart::{color, escape_xml};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Default export dir, also where the server looks after a restart.
+const CARD_DIR: &str = "data/cards";
+const CARD_WIDTH: f64 = 495.0;
+
+// latest card of every service, what the http server hands out
+static CARDS: LazyLock<RwLock<HashMap<String, String>>> = LazyLock::new(Default::default);
+
+/// Per-service `card` block of the config.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CardSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where to write the svg, "" = data/cards/<service>.svg.
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub theme: CardTheme,
+    /// Overrides single colors of the theme.
+    #[serde(default)]
+    pub colors: ThemeColors,
+}
+
+impl CardSettings {
+    pub fn output_path(&self, service: &str) -> PathBuf {
+        if self.path.is_empty() {
+            Path::new(CARD_DIR).join(format!("{}.svg", service))
+        } else {
+            PathBuf::from(&self.path)
+        }
+    }
+
+    fn palette(&self) -> Palette {
+        let base = self.theme.palette();
+        let pick = |custom: &Option<String>, default: &'static str| {
+            custom.clone().unwrap_or_else(|| default.to_string())
+        };
+
+        Palette {
+            background: pick(&self.colors.background, base.background),
+            border: pick(&self.colors.border, base.border),
+            title: pick(&self.colors.title, base.title),
+            text: pick(&self.colors.text, base.text),
+            muted: pick(&self.colors.muted, base.muted),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CardTheme {
+    #[default]
+    Light,
+    Dark,
+    Dracula,
+    Transparent,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ThemeColors {
+    pub background: Option<String>,
+    pub border: Option<String>,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub muted: Option<String>,
+}
+
+struct BasePalette {
+    background: &'static str,
+    border: &'static str,
+    title: &'static str,
+    text: &'static str,
+    muted: &'static str,
+}
+
+struct Palette {
+    background: String,
+    border: String,
+    title: String,
+    text: String,
+    muted: String,
+}
+
+impl CardTheme {
+    fn palette(self) -> BasePalette {
+        match self {
+            CardTheme::Light => BasePalette {
+                background: "#fffefe",
+                border: "#e4e2e2",
+                title: "#2f80ed",
+                text: "#434d58",
+                muted: "#858d96",
+            },
+            CardTheme::Dark => BasePalette {
+                background: "#151515",
+                border: "#2c2c2c",
+                title: "#ffffff",
+                text: "#9f9f9f",
+                muted: "#6e6e6e",
+            },
+            CardTheme::Dracula => BasePalette {
+                background: "#282a36",
+                border: "#44475a",
+                title: "#ff6e96",
+                text: "#f8f8f2",
+                muted: "#6272a4",
+            },
+            CardTheme::Transparent => BasePalette {
+                background: "none",
+                border: "none",
+                title: "#006aff",
+                text: "#417e87",
+                muted: "#7e8f9e",
+            },
+        }
+    }
+}
+
+/// Numbers a service puts on its card.
+#[derive(Debug, Clone, Default)]
+pub struct StatsCard {
+    pub title: String,
+    pub totals: Vec<(String, String)>, // label -> value
+    pub languages: Vec<(String, f64)>, // name -> percentage, biggest first
+    pub last_activity: String,
+}
+
+pub fn render_svg(card: &StatsCard, settings: &CardSettings) -> String {
+    let palette = settings.palette();
+    let langs_top = 60.0 + card.totals.len() as f64 * 24.0 + 16.0;
+    let height = langs_top + card.languages.len() as f64 * 32.0 + 44.0;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Segoe UI, Ubuntu, Helvetica, Arial, sans-serif">"#,
+        w = CARD_WIDTH,
+        h = height
+    );
+    let _ = write!(
+        svg,
+        r#"<rect x="0.5" y="0.5" rx="6" width="{}" height="{}" fill="{}" stroke="{}"/>"#,
+        CARD_WIDTH - 1.0,
+        height - 1.0,
+        palette.background,
+        palette.border
+    );
+    let _ = write!(
+        svg,
+        r#"<text x="25" y="35" font-size="18" font-weight="600" fill="{}">{}</text>"#,
+        palette.title,
+        escape_xml(&card.title)
+    );
+
+    for (i, (label, value)) in card.totals.iter().enumerate() {
+        let y = 68.0 + i as f64 * 24.0;
+        let _ = write!(
+            svg,
+            r#"<text x="25" y="{y}" font-size="14" fill="{c}">{}:</text><text x="220" y="{y}" font-size="14" font-weight="700" fill="{c}">{}</text>"#,
+            escape_xml(label),
+            escape_xml(value),
+            c = palette.text
+        );
+    }
+
+    let bar_width = CARD_WIDTH - 50.0;
+    for (i, (name, share)) in card.languages.iter().enumerate() {
+        let y = langs_top + i as f64 * 32.0;
+        let _ = write!(
+            svg,
+            r#"<text x="25" y="{}" font-size="12" fill="{}">{} {:.2}%</text><rect x="25" y="{}" width="{}" height="8" rx="4" fill="{}" opacity="0.25"/><rect x="25" y="{}" width="{:.2}" height="8" rx="4" fill="{}"/>"#,
+            y + 10.0,
+            palette.text,
+            escape_xml(name),
+            share,
+            y + 16.0,
+            bar_width,
+            palette.muted,
+            y + 16.0,
+            bar_width * share.clamp(0.0, 100.0) / 100.0,
+            color(i)
+        );
+    }
+
+    if !card.last_activity.is_empty() {
+        let _ = write!(
+            svg,
+            r#"<text x="25" y="{}" font-size="11" fill="{}">Last activity: {}</text>"#,
+            height - 16.0,
+            palette.muted,
+            escape_xml(&card.last_activity)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Writes the service's card to disk and makes it available to the server.
+pub fn export(service: &str, svg: String, settings: &CardSettings) -> Result<()> {
+    let path = settings.output_path(service);
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, &svg)?;
+
+    CARDS.write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(service.to_string(), svg);
+
+    Ok(())
+}
+
+/// Global `card_server` block: serves `GET /<service>.svg`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CardServer {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_listen")]
+    pub listen: String,
+    /// `Cache-Control: max-age` for READMEs behind image proxies.
+    #[serde(default = "default_max_age")]
+    pub max_age: u64,
+}
+
+fn default_listen() -> String {
+    "127.0.0.1:8080".to_string()
+}
+
+fn default_max_age() -> u64 {
+    3600
+}
+
+impl Default for CardServer {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_listen(),
+            max_age: default_max_age(),
+        }
+    }
+}
+
+impl CardServer {
+    pub async fn serve(self) -> Result<()> {
+        let listener = TcpListener::bind(&self.listen).await?;
+        log::info!("serving stats cards on http://{}", self.listen);
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let max_age = self.max_age;
+            tokio::spawn(async move {
+                if let Err(e) = respond(stream, max_age).await {
+                    log::warn!("card request failed: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// Latest card of `service`, from memory or, after a restart, from the
+/// file an earlier run exported to `dir`.
+async fn find_card(dir: &Path, service: &str) -> Option<String> {
+    let cached = CARDS.read().unwrap_or_else(|e| e.into_inner()).get(service).cloned();
+    match cached {
+        Some(svg) => Some(svg),
+        None => tokio::fs::read_to_string(dir.join(format!("{}.svg", service))).await.ok(),
+    }
+}
+
+async fn respond(mut stream: TcpStream, max_age: u64) -> Result<()> {
+    // the request line is all we look at
+    let mut buf = [0u8; 1024];
+    let read = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let mut parts = request.split_whitespace();
+
+    let card = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => {
+            let path = target.split('?').next().unwrap_or_default();
+            match Path::new(path).file_stem().and_then(|name| name.to_str()) {
+                Some(service) if path.ends_with(".svg") => find_card(Path::new(CARD_DIR), service).await,
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    let response = match card {
+        Some(svg) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: image/svg+xml; charset=utf-8\r\nCache-Control: max-age={}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            max_age,
+            svg.len(),
+            svg
+        ),
+        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn serves_exported_file_when_not_in_memory() {
+        let dir = std::env::temp_dir().join(format!("tele-stats-cards-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("card-test.svg");
+        fs::write(&path, "<svg/>").unwrap();
+
+        let card = find_card(&dir, "card-test").await;
+        fs::remove_file(&path).unwrap();
+        assert_eq!(card.as_deref(), Some("<svg/>"));
+        assert_eq!(find_card(&dir, "card-test").await, None);
+        fs::remove_dir(&dir).unwrap();
+    }
+}
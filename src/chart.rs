@@ -0,0 +1,240 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::{Result, TeleStatsError};
+use chrono::DateTime;
+use resvg::{tiny_skia, usvg};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+const WIDTH: f64 = 800.0;
+const LANG_HEIGHT: f64 = 420.0;
+const HISTORY_HEIGHT: f64 = 300.0;
+
+const PALETTE: [&str; 12] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948",
+    "#b07aa1", "#ff9da7", "#9c755f", "#bab0ac", "#86bcb6", "#d37295",
+];
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartKind {
+    #[default]
+    Pie,
+    Bar,
+}
+
+/// Per-service `chart` block of the config.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChartSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub kind: ChartKind,
+    /// Lines-over-time panel covering this many days of history, 0 = off.
+    #[serde(default = "default_history_days")]
+    pub history_days: u32,
+    #[serde(default = "default_font_family")]
+    pub font_family: String,
+    /// Extra font file for hosts without system fonts.
+    #[serde(default)]
+    pub font_path: Option<String>,
+}
+
+fn default_history_days() -> u32 {
+    30
+}
+
+fn default_font_family() -> String {
+    "DejaVu Sans".to_string()
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: ChartKind::default(),
+            history_days: default_history_days(),
+            font_family: default_font_family(),
+            font_path: None,
+        }
+    }
+}
+
+pub fn color(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+        .replace("\"", "&quot;")
+}
+
+/// Draws the language distribution and, given at least two points of
+/// `(unix time, total lines)`, the lines-over-time panel under it.
+pub fn stats_chart_svg(kind: ChartKind, languages: &[(String, f64)], series: &[(i64, usize)]) -> String {
+    let with_history = series.len() >= 2;
+    let height = if with_history { LANG_HEIGHT + HISTORY_HEIGHT } else { LANG_HEIGHT };
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+        w = WIDTH,
+        h = height
+    );
+    let _ = write!(svg, r##"<rect width="100%" height="100%" fill="#ffffff"/>"##);
+    let _ = write!(svg, r##"<text x="24" y="36" font-size="22" font-weight="bold" fill="#222">Languages</text>"##);
+
+    match kind {
+        ChartKind::Pie => pie(&mut svg, languages),
+        ChartKind::Bar => bars(&mut svg, languages),
+    }
+
+    if with_history {
+        history(&mut svg, series, LANG_HEIGHT);
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn pie(svg: &mut String, languages: &[(String, f64)]) {
+    let (cx, cy, r) = (210.0, 230.0, 160.0);
+    let total: f64 = languages.iter().map(|(_, share)| share).sum();
+    if total <= 0.0 {
+        return;
+    }
+
+    let mut angle = -PI / 2.0; // start at 12 o'clock
+    for (i, (_, share)) in languages.iter().enumerate() {
+        let sweep = share / total * 2.0 * PI;
+        if sweep >= 2.0 * PI - 1e-6 {
+            let _ = write!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#, cx, cy, r, color(i));
+            break;
+        }
+
+        let (x0, y0) = (cx + r * angle.cos(), cy + r * angle.sin());
+        angle += sweep;
+        let (x1, y1) = (cx + r * angle.cos(), cy + r * angle.sin());
+        let large = if sweep > PI { 1 } else { 0 };
+        let _ = write!(
+            svg,
+            r##"<path d="M{cx:.2} {cy:.2} L{x0:.2} {y0:.2} A{r} {r} 0 {large} 1 {x1:.2} {y1:.2} Z" fill="{c}" stroke="#ffffff" stroke-width="2"/>"##,
+            c = color(i)
+        );
+    }
+
+    let row = (320.0 / languages.len() as f64).min(30.0);
+    for (i, (name, share)) in languages.iter().enumerate() {
+        let y = 70.0 + i as f64 * row;
+        let _ = write!(
+            svg,
+            r##"<rect x="420" y="{}" width="18" height="18" rx="3" fill="{}"/><text x="448" y="{}" font-size="17" fill="#333">{} — {:.2}%</text>"##,
+            y,
+            color(i),
+            y + 15.0,
+            escape_xml(name),
+            share
+        );
+    }
+}
+
+fn bars(svg: &mut String, languages: &[(String, f64)]) {
+    let max = languages.iter().map(|(_, share)| *share).fold(0.0, f64::max);
+    if max <= 0.0 {
+        return;
+    }
+
+    let row = ((LANG_HEIGHT - 80.0) / languages.len().max(1) as f64).min(40.0);
+    for (i, (name, share)) in languages.iter().enumerate() {
+        let y = 60.0 + i as f64 * row;
+        let width = share / max * 460.0;
+        let _ = write!(
+            svg,
+            r##"<text x="24" y="{}" font-size="16" fill="#333">{}</text><rect x="210" y="{}" width="{:.2}" height="{}" rx="4" fill="{}"/><text x="{:.2}" y="{}" font-size="15" fill="#555">{:.2}%</text>"##,
+            y + row * 0.6,
+            escape_xml(name),
+            y + row * 0.15,
+            width,
+            row * 0.6,
+            color(i),
+            218.0 + width,
+            y + row * 0.6,
+            share
+        );
+    }
+}
+
+fn history(svg: &mut String, series: &[(i64, usize)], top: f64) {
+    let (left, right) = (90.0, WIDTH - 30.0);
+    let (upper, lower) = (top + 50.0, top + HISTORY_HEIGHT - 40.0);
+
+    let (t0, t1) = (series[0].0, series[series.len() - 1].0);
+    let min = series.iter().map(|(_, lines)| *lines).min().unwrap_or(0);
+    let max = series.iter().map(|(_, lines)| *lines).max().unwrap_or(0);
+    let span_t = (t1 - t0).max(1) as f64;
+    let span_v = (max - min).max(1) as f64;
+
+    let points: Vec<String> = series
+        .iter()
+        .map(|(t, lines)| {
+            let x = left + (t - t0) as f64 / span_t * (right - left);
+            let y = lower - (lines - min) as f64 / span_v * (lower - upper);
+            format!("{:.2},{:.2}", x, y)
+        })
+        .collect();
+
+    let date = |t: i64| {
+        DateTime::from_timestamp(t, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+
+    let _ = write!(
+        svg,
+        r##"<text x="24" y="{title}" font-size="22" font-weight="bold" fill="#222">Lines of code</text>
+<line x1="{left}" y1="{lower}" x2="{right}" y2="{lower}" stroke="#bbb"/>
+<line x1="{left}" y1="{upper}" x2="{left}" y2="{lower}" stroke="#bbb"/>
+<text x="{lx}" y="{upper_t}" font-size="13" fill="#555" text-anchor="end">{max}</text>
+<text x="{lx}" y="{lower}" font-size="13" fill="#555" text-anchor="end">{min}</text>
+<text x="{left}" y="{dates}" font-size="13" fill="#555">{d0}</text>
+<text x="{right}" y="{dates}" font-size="13" fill="#555" text-anchor="end">{d1}</text>
+<polyline points="{points}" fill="none" stroke="{c}" stroke-width="3"/>"##,
+        title = top + 30.0,
+        lx = left - 8.0,
+        upper_t = upper + 5.0,
+        dates = lower + 22.0,
+        d0 = date(t0),
+        d1 = date(t1),
+        points = points.join(" "),
+        c = color(0)
+    );
+}
+
+/// Rasterizes an SVG into a PNG, fonts come from the system plus
+/// `font_path` if set.
+pub fn render_png(svg: &str, settings: &ChartSettings) -> Result<Vec<u8>> {
+    let mut options = usvg::Options {
+        font_family: settings.font_family.clone(),
+        ..usvg::Options::default()
+    };
+    let fontdb = options.fontdb_mut();
+    fontdb.load_system_fonts();
+    if let Some(path) = settings.font_path.as_deref().filter(|p| !p.is_empty()) {
+        fontdb.load_font_file(path)?;
+    }
+    fontdb.set_sans_serif_family(settings.font_family.clone());
+
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| TeleStatsError::Chart(format!("invalid svg: {}", e)))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| TeleStatsError::Chart("empty chart".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| TeleStatsError::Chart(format!("png encoding failed: {}", e)))
+}
@@ -0,0 +1,96 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{CombinedConfig, CombinedStats};
+use crate::card::StatsCard;
+use crate::forge::formatter::{self, escape_markdown, format_activity, forge_label, MessageOptions};
+use crate::services::RenderContext;
+use chrono::Local;
+
+fn forge_list(stats: &CombinedStats) -> String {
+    stats.forges
+        .iter()
+        .map(|forge| forge_label(&forge.forge))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+pub fn format_stats(stats: &CombinedStats, config: &CombinedConfig, context: &RenderContext) -> String {
+    let now = Local::now();
+    let date = now.format("%Y\\-%m\\-%d").to_string();
+    let time = now.format("%H:%M:%S").to_string();
+    let merged = &stats.merged;
+
+    let mut total_lines = merged.total_lines.to_string();
+    if let Some(baseline) = &context.baseline {
+        let delta = merged.total_lines as i64 - baseline.total_lines as i64;
+        total_lines.push_str(&format!(" ({:+} {})", delta, context.period));
+    }
+
+    let mut repos = merged.total_repos.to_string();
+    if stats.mirrored_repos > 0 {
+        repos.push_str(&format!(" ({} mirrors counted once)", stats.mirrored_repos));
+    }
+
+    let mut message = format!(
+        "Combined statistics across {}\n\
+        *Total code lines:* {}\n\
+        *Comment lines:* {}\n\
+        *Blank lines:* {}\n\
+        *Last updated:* {} \\| {}\n\
+        *Total languages:* {}\n\
+        *Favorite language:* {}\n\
+        *Repositories:* {}\n\
+        *Public repositories:* {}\n\
+        *Last activity:* {}\n\n\
+        *By forge*",
+        escape_markdown(&forge_list(stats)),
+        escape_markdown(&total_lines),
+        merged.line_counts.comments,
+        merged.line_counts.blanks,
+        date,
+        time,
+        escape_markdown(&merged.total_languages.to_string()),
+        escape_markdown(&merged.favorite_language),
+        escape_markdown(&repos),
+        escape_markdown(&merged.public_repos.to_string()),
+        escape_markdown(&format_activity(&merged.last_activity))
+    );
+
+    let bullet = if config.quote { ">•" } else { "•" };
+    for forge in &stats.forges {
+        message.push_str(&format!(
+            "\n{} {}: {} lines, {} repos, mostly {}",
+            bullet,
+            escape_markdown(forge_label(&forge.forge)),
+            forge.total_lines,
+            forge.total_repos,
+            escape_markdown(&forge.favorite_language)
+        ));
+    }
+
+    message.push_str("\n\n*Languages*");
+    let options = MessageOptions {
+        quote: config.quote,
+        top_repos: &config.top_repos,
+    };
+    message.push_str(&formatter::format_languages(merged, config.quote, context));
+    message.push_str(&formatter::format_top_repos(merged, &options));
+    message.push_str(&formatter::format_incomplete(merged));
+    message
+}
+
+pub fn stats_card(stats: &CombinedStats) -> StatsCard {
+    let merged = &stats.merged;
+    StatsCard {
+        title: format!("Stats across {}", forge_list(stats)),
+        totals: vec![
+            ("Total code lines".to_string(), merged.total_lines.to_string()),
+            ("Repositories".to_string(), format!("{} ({} public)", merged.total_repos, merged.public_repos)),
+            ("Languages".to_string(), merged.total_languages.to_string()),
+            ("Favorite language".to_string(), merged.favorite_language.clone()),
+        ],
+        languages: merged.language_shares(),
+        last_activity: format_activity(&merged.last_activity),
+    }
+}
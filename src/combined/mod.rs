@@ -0,0 +1,78 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod analyzer;
+pub mod formatter;
+
+use crate::card::{CardSettings, StatsCard};
+use crate::chart::ChartSettings;
+use crate::error::{Result, TeleStatsError};
+use crate::forge::store;
+use crate::history::{HistorySettings, Snapshot};
+use crate::services::{RenderContext, StatsService};
+use async_trait::async_trait;
+use formatter::{format_stats, stats_card};
+use types::{CombinedConfig, CombinedStats};
+
+/// One post for every forge together, built from the stats the forge
+/// services stored on their last run (so it is registered after them).
+pub struct CombinedService;
+
+#[async_trait]
+impl StatsService for CombinedService {
+    type Config = CombinedConfig;
+    type Stats = CombinedStats;
+
+    fn name(&self) -> &'static str {
+        "combined"
+    }
+
+    fn message_id(&self, config: &CombinedConfig) -> i64 {
+        config.message_id
+    }
+
+    async fn collect_stats(&self, config: &CombinedConfig) -> Result<CombinedStats> {
+        let mut forges = Vec::new();
+        for forge in &config.forges {
+            match store::load(forge)? {
+                Some(stats) => forges.push((forge.clone(), stats)),
+                None => log::warn!("no {} stats yet, leaving it out of the combined post", forge),
+            }
+        }
+
+        if forges.is_empty() {
+            return Err(TeleStatsError::NotFound("none of the combined forges has finished a run yet".to_string()));
+        }
+
+        Ok(analyzer::merge(forges, config.dedup_mirrors, config.dedup_by_name, config.max_langs))
+    }
+
+    fn history_settings(&self, config: &CombinedConfig) -> HistorySettings {
+        config.history.clone()
+    }
+
+    fn snapshot(&self, stats: &CombinedStats) -> Option<Snapshot> {
+        Some(stats.merged.snapshot())
+    }
+
+    fn chart_settings(&self, config: &CombinedConfig) -> ChartSettings {
+        config.chart.clone()
+    }
+
+    fn chart_languages(&self, stats: &CombinedStats) -> Vec<(String, f64)> {
+        stats.merged.language_shares()
+    }
+
+    fn card_settings(&self, config: &CombinedConfig) -> CardSettings {
+        config.card.clone()
+    }
+
+    fn stats_card(&self, stats: &CombinedStats, _config: &CombinedConfig) -> Option<StatsCard> {
+        Some(stats_card(stats))
+    }
+
+    fn render_message(&self, stats: &CombinedStats, config: &CombinedConfig, context: &RenderContext) -> Result<String> {
+        Ok(format_stats(stats, config, context))
+    }
+}
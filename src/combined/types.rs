@@ -0,0 +1,57 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::card::CardSettings;
+use crate::chart::ChartSettings;
+use crate::forge::types::{ForgeStats, TopReposSettings};
+use crate::history::HistorySettings;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CombinedConfig {
+    pub message_id: i64,
+    pub quote: bool,
+    pub max_langs: usize,
+    #[serde(default)]
+    pub top_repos: TopReposSettings,
+    /// Forge services to merge. Of a mirrored repo the copy with more lines
+    /// is kept, ties go to the forge listed first.
+    #[serde(default = "default_forges")]
+    pub forges: Vec<String>,
+    /// Count a mirror or import once, matched by its upstream url.
+    #[serde(default = "default_dedup_mirrors")]
+    pub dedup_mirrors: bool,
+    /// Also treat repos that merely share a name as the same project.
+    #[serde(default)]
+    pub dedup_by_name: bool,
+    #[serde(default)]
+    pub history: HistorySettings,
+    #[serde(default)]
+    pub chart: ChartSettings,
+    #[serde(default)]
+    pub card: CardSettings,
+}
+
+fn default_forges() -> Vec<String> {
+    vec!["github".to_string(), "gitlab".to_string()]
+}
+
+fn default_dedup_mirrors() -> bool {
+    true
+}
+
+/// One forge's own numbers, before mirrors are merged away.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForgeBreakdown {
+    pub forge: String, // service name, e.g. "github"
+    pub total_lines: usize,
+    pub total_repos: usize,
+    pub favorite_language: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CombinedStats {
+    pub merged: ForgeStats,
+    pub forges: Vec<ForgeBreakdown>,
+    pub mirrored_repos: usize, // counted once although present on several forges
+}
@@ -1,14 +1,21 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
+use crate::error::Result;
+use crate::forge::{formatter, store};
 use teloxide::{prelude::*, utils::command::BotCommands};
 use teloxide::requests::Requester;
 
+// telegram rejects longer messages, counted in utf-16 units
+const MESSAGE_LIMIT: usize = 4096;
+
 #[derive(BotCommands, Clone)]
 #[command(rename_rule = "lowercase")]
 pub enum Command {
     #[command(description = "show bot information")]
     Start,
+    #[command(description = "show one repository's languages: /repo <name>")]
+    Repo(String),
 }
 
 pub async fn handle_command(
@@ -16,7 +23,7 @@ pub async fn handle_command(
     msg: Message,
     cmd: Command,
     tg_username: String, // import from cfg
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<()> {
     match cmd {
         Command::Start => {
             let message = format!(
@@ -28,6 +35,13 @@ pub async fn handle_command(
                 tg_username.replace("-", "\\-").replace(".", "\\.") // markdown(((((
             );
 
+            bot.send_message(msg.chat.id, message)
+                .parse_mode(teloxide::types::ParseMode::MarkdownV2)
+                .await?;
+        }
+        Command::Repo(name) => {
+            let message = repo_message(name.trim(), is_owner(&msg, &tg_username))?;
+
             bot.send_message(msg.chat.id, message)
                 .parse_mode(teloxide::types::ParseMode::MarkdownV2)
                 .await?;
@@ -35,4 +49,79 @@ pub async fn handle_command(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// private repos are only shown to whoever hosts the bot
+fn is_owner(msg: &Message, tg_username: &str) -> bool {
+    msg.from
+        .as_ref()
+        .and_then(|user| user.username.as_deref())
+        .is_some_and(|username| username.eq_ignore_ascii_case(tg_username.trim_start_matches('@')))
+}
+
+fn repo_message(name: &str, show_private: bool) -> Result<String> {
+    if name.is_empty() {
+        return Ok("Usage: /repo <name\\>".to_string());
+    }
+
+    let found: Vec<String> = store::load_all()?
+        .iter()
+        .flat_map(|(service, stats)| {
+            stats.repos
+                .iter()
+                .filter(|repo| repo.name.eq_ignore_ascii_case(name))
+                .filter(|repo| show_private || !repo.private)
+                .map(|repo| formatter::format_repo(service, repo))
+        })
+        .collect();
+
+    if found.is_empty() {
+        return Ok(format!("Repository {} not found", formatter::escape_markdown(name)));
+    }
+
+    Ok(join_within_limit(found))
+}
+
+/// Joins the copies of a repo, leaving out whatever doesn't fit in one message.
+fn join_within_limit(parts: Vec<String>) -> String {
+    let total = parts.len();
+    let mut message = String::new();
+    for (shown, part) in parts.into_iter().enumerate() {
+        let note = format!("\n\n_and {} more copies_", total - shown);
+        let separator = if message.is_empty() { "" } else { "\n\n" };
+        let room = MESSAGE_LIMIT.saturating_sub(utf16_len(&message) + utf16_len(separator));
+        let last = shown + 1 == total;
+        if utf16_len(&part) + if last { 0 } else { utf16_len(&note) } > room {
+            if !message.is_empty() {
+                message.push_str(&note);
+            }
+            break;
+        }
+        message.push_str(separator);
+        message.push_str(&part);
+    }
+    message
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_that_fit_are_all_shown() {
+        let message = join_within_limit(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(message, "a\n\nb");
+    }
+
+    #[test]
+    fn long_replies_stop_at_the_message_limit() {
+        let parts = vec!["x".repeat(1500); 5];
+        let message = join_within_limit(parts);
+        assert!(utf16_len(&message) <= MESSAGE_LIMIT);
+        assert!(message.ends_with("_and 3 more copies_"), "{}", message);
+    }
+}
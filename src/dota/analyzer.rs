@@ -0,0 +1,208 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::client::DotaClient;
+use super::types::{DotaMatch, DotaStats, HeroStats, PlayerData};
+use crate::error::{Result, TeleStatsError};
+use std::collections::HashMap;
+
+const MEDALS: &[&str] = &["Herald", "Guardian", "Crusader", "Archon", "Legend", "Ancient", "Divine", "Immortal"];
+const MMR_PER_STAR: u32 = 154; // roughly, ranked medals are ~770 mmr apart
+
+pub struct DotaAnalyzer {
+    client: DotaClient,
+    account_id: u64,
+    vanity: String,
+    heroes: usize,
+    recent_matches: usize,
+}
+
+impl DotaAnalyzer {
+    pub fn new(client: DotaClient, account_id: u64, vanity: &str, heroes: usize, recent_matches: usize) -> Self {
+        Self {
+            client,
+            account_id,
+            vanity: vanity.to_string(),
+            heroes,
+            recent_matches,
+        }
+    }
+
+    pub async fn analyze(&self) -> Result<DotaStats> {
+        let account_id = self.client.resolve_account(self.account_id, &self.vanity).await?;
+        let data = self.client.get_player_data(account_id).await?;
+        build_stats(data, self.heroes, self.recent_matches)
+    }
+}
+
+/// Turns OpenDota's responses into the post's numbers, no requests involved.
+pub fn build_stats(data: PlayerData, heroes: usize, recent_matches: usize) -> Result<DotaStats> {
+    let Some(profile) = data.player.profile else {
+        return Err(TeleStatsError::NotFound("opendota has no public profile for this account, expose public match data in dota settings".to_string()));
+    };
+
+    let names: HashMap<u32, String> = data.hero_names
+        .into_iter()
+        .map(|hero| (hero.id, hero.localized_name))
+        .collect();
+    let hero_name = |id: u32| names.get(&id).cloned().unwrap_or_else(|| format!("Hero #{}", id));
+
+    let mut played = data.heroes;
+    played.sort_by_key(|hero| std::cmp::Reverse(hero.games));
+    let heroes = played
+        .into_iter()
+        .filter(|hero| hero.games > 0)
+        .take(heroes)
+        .map(|hero| HeroStats {
+            name: hero_name(hero.hero_id),
+            games: hero.games,
+            wins: hero.win,
+        })
+        .collect();
+
+    let recent = data.recent
+        .into_iter()
+        .take(recent_matches)
+        .map(|game| DotaMatch {
+            hero: hero_name(game.hero_id),
+            won: game.radiant_win == Some(game.player_slot < 128), // slots 128+ are dire
+            kills: game.kills,
+            deaths: game.deaths,
+            assists: game.assists,
+            start_time: game.start_time,
+        })
+        .collect();
+
+    let tier = data.player.rank_tier.unwrap_or(0);
+    let estimate = data.player.mmr_estimate
+        .and_then(|mmr| mmr.estimate)
+        .map(|estimate| estimate.round() as u32)
+        .or_else(|| tier_mmr(tier));
+
+    Ok(DotaStats {
+        account_id: profile.account_id,
+        name: profile.personaname.unwrap_or_else(|| profile.account_id.to_string()),
+        country: profile.loccountrycode.unwrap_or_default(),
+        profile_url: format!("https://www.opendota.com/players/{}", profile.account_id),
+        rank: rank_name(tier, data.player.leaderboard_rank),
+        mmr_estimate: estimate,
+        wins: data.win_loss.win,
+        losses: data.win_loss.lose,
+        heroes,
+        recent,
+    })
+}
+
+/// rank_tier is medal * 10 + stars, e.g. 53 = Legend 3.
+fn rank_name(tier: u32, leaderboard: Option<u32>) -> String {
+    let medal = (tier / 10) as usize;
+    let stars = tier % 10;
+    match MEDALS.get(medal.wrapping_sub(1)) {
+        None => "Uncalibrated".to_string(),
+        Some(&"Immortal") => match leaderboard {
+            Some(place) => format!("Immortal #{}", place),
+            None => "Immortal".to_string(),
+        },
+        Some(name) => format!("{} {}", name, stars),
+    }
+}
+
+/// Middle of the medal's mmr range, for accounts OpenDota has no estimate for.
+fn tier_mmr(tier: u32) -> Option<u32> {
+    let medal = tier / 10;
+    let stars = (tier % 10).clamp(1, 5);
+    match medal {
+        1..=7 => Some(((medal - 1) * 5 + stars - 1) * MMR_PER_STAR + MMR_PER_STAR / 2),
+        8 => Some(35 * MMR_PER_STAR), // immortal floor
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> T {
+        let path = format!("{}/tests/fixtures/opendota/{}", env!("CARGO_MANIFEST_DIR"), name);
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+    }
+
+    fn player_data(player: &str) -> PlayerData {
+        PlayerData {
+            player: fixture(player),
+            win_loss: fixture("wl.json"),
+            heroes: fixture("player_heroes.json"),
+            hero_names: fixture("heroes.json"),
+            recent: fixture("recent_matches.json"),
+        }
+    }
+
+    #[test]
+    fn builds_stats_from_opendota_responses() {
+        let stats = build_stats(player_data("player.json"), 2, 20).unwrap();
+
+        assert_eq!(stats.name, "Arteezy [GG]");
+        assert_eq!(stats.country, "CA");
+        assert_eq!(stats.profile_url, "https://www.opendota.com/players/86745912");
+        assert_eq!(stats.rank, "Legend 3");
+        assert_eq!(stats.mmr_estimate, Some(3513));
+        assert_eq!((stats.wins, stats.losses), (4121, 3602));
+
+        let heroes: Vec<(&str, u64)> = stats.heroes.iter().map(|hero| (hero.name.as_str(), hero.games)).collect();
+        assert_eq!(heroes, [("Invoker", 512), ("Anti-Mage", 420)]);
+
+        let results: Vec<(&str, bool)> = stats.recent.iter().map(|game| (game.hero.as_str(), game.won)).collect();
+        assert_eq!(results, [("Invoker", true), ("Anti-Mage", false), ("Hero #99", true)]);
+    }
+
+    #[test]
+    fn unplayed_heroes_and_extra_matches_are_left_out() {
+        let stats = build_stats(player_data("player.json"), 10, 2).unwrap();
+        assert_eq!(stats.heroes.len(), 3); // Mars has 0 games
+        assert_eq!(stats.recent.len(), 2);
+    }
+
+    #[test]
+    fn uncalibrated_account_has_no_mmr() {
+        let stats = build_stats(player_data("player_uncalibrated.json"), 5, 20).unwrap();
+        assert_eq!(stats.rank, "Uncalibrated");
+        assert_eq!(stats.mmr_estimate, None);
+        assert_eq!(stats.name, "1234567"); // no persona name
+        assert_eq!(stats.country, "");
+    }
+
+    #[test]
+    fn immortal_falls_back_to_the_medal_floor() {
+        let stats = build_stats(player_data("player_immortal.json"), 5, 20).unwrap();
+        assert_eq!(stats.rank, "Immortal #512");
+        assert_eq!(stats.mmr_estimate, Some(35 * MMR_PER_STAR));
+    }
+
+    #[test]
+    fn private_profile_is_not_found() {
+        let result = build_stats(player_data("player_private.json"), 5, 20);
+        assert!(matches!(result, Err(TeleStatsError::NotFound(_))));
+    }
+
+    #[test]
+    fn rank_names() {
+        assert_eq!(rank_name(0, None), "Uncalibrated");
+        assert_eq!(rank_name(11, None), "Herald 1");
+        assert_eq!(rank_name(53, None), "Legend 3");
+        assert_eq!(rank_name(75, None), "Divine 5");
+        assert_eq!(rank_name(80, None), "Immortal");
+        assert_eq!(rank_name(80, Some(7)), "Immortal #7");
+        assert_eq!(rank_name(95, None), "Uncalibrated"); // unknown medal
+    }
+
+    #[test]
+    fn tier_mmr_is_the_middle_of_the_star() {
+        assert_eq!(tier_mmr(0), None);
+        assert_eq!(tier_mmr(11), Some(77));
+        assert_eq!(tier_mmr(53), Some(3465));
+        assert_eq!(tier_mmr(50), tier_mmr(51)); // no stars yet counts as one
+        assert_eq!(tier_mmr(80), Some(5390));
+        assert_eq!(tier_mmr(90), None);
+    }
+}
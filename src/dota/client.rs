@@ -0,0 +1,107 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{steam32, DotaConfig, Hero, PlayerData, PlayerHero, PlayerResponse, RecentMatch, VanityResponse, WinLoss};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::http::ForgeHttp;
+use reqwest::{Client, header};
+use serde::de::DeserializeOwned;
+
+pub struct DotaClient {
+    http: ForgeHttp,
+    base_url: String,
+    steam_url: String,
+    steam_key: String,
+}
+
+impl DotaClient {
+    pub fn new(config: &DotaConfig) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        if !config.api_key.is_empty() {
+            headers.insert(
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&format!("Bearer {}", config.api_key))?, // kept out of logged urls
+            );
+        }
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self {
+            http: ForgeHttp::new(client, config.max_retries),
+            base_url: config.api_root().to_string(),
+            steam_url: config.steam_api_root().to_string(),
+            steam_key: config.steam_api_key.clone(),
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str, context: &str) -> Result<T> {
+        let response = self.http.get(url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), context));
+        }
+
+        Ok(response.json().await.map_err(reqwest::Error::without_url)?) // the steam key is in the query
+    }
+
+    /// Steam32 id of the configured account, looking the vanity name up if needed.
+    pub async fn resolve_account(&self, account_id: u64, vanity: &str) -> Result<u64> {
+        if account_id != 0 {
+            return Ok(steam32(account_id));
+        }
+        if vanity.is_empty() || self.steam_key.is_empty() {
+            return Err(TeleStatsError::Config("set account_id, or steam_vanity together with steam_api_key".to_string()));
+        }
+
+        let url = format!(
+            "{}/ISteamUser/ResolveVanityURL/v1/?key={}&vanityurl={}",
+            self.steam_url, self.steam_key, urlencoding::encode(vanity)
+        );
+        let resolved: VanityResponse = self.get_json(&url, "failed to resolve steam vanity name").await?;
+
+        match resolved.response.steamid.and_then(|id| id.parse().ok()) {
+            Some(steam64) if resolved.response.success == 1 => Ok(steam32(steam64)),
+            _ => Err(TeleStatsError::NotFound(format!("steam profile {}", vanity))),
+        }
+    }
+
+    pub async fn get_player(&self, account_id: u64) -> Result<PlayerResponse> {
+        let url = format!("{}/players/{}", self.base_url, account_id);
+        self.get_json(&url, "failed to fetch player").await
+    }
+
+    pub async fn get_win_loss(&self, account_id: u64) -> Result<WinLoss> {
+        let url = format!("{}/players/{}/wl", self.base_url, account_id);
+        self.get_json(&url, "failed to fetch win/loss").await
+    }
+
+    pub async fn get_player_heroes(&self, account_id: u64) -> Result<Vec<PlayerHero>> {
+        let url = format!("{}/players/{}/heroes", self.base_url, account_id);
+        self.get_json(&url, "failed to fetch heroes").await
+    }
+
+    pub async fn get_heroes(&self) -> Result<Vec<Hero>> {
+        let url = format!("{}/heroes", self.base_url);
+        self.get_json(&url, "failed to fetch hero names").await
+    }
+
+    pub async fn get_recent_matches(&self, account_id: u64) -> Result<Vec<RecentMatch>> {
+        let url = format!("{}/players/{}/recentMatches", self.base_url, account_id);
+        self.get_json(&url, "failed to fetch recent matches").await
+    }
+
+    /// All of a player's endpoints, fetched side by side.
+    pub async fn get_player_data(&self, account_id: u64) -> Result<PlayerData> {
+        let (player, win_loss, heroes, hero_names, recent) = tokio::try_join!(
+            self.get_player(account_id),
+            self.get_win_loss(account_id),
+            self.get_player_heroes(account_id),
+            self.get_heroes(),
+            self.get_recent_matches(account_id),
+        )?;
+
+        Ok(PlayerData { player, win_loss, heroes, hero_names, recent })
+    }
+}
@@ -0,0 +1,106 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{DotaConfig, DotaStats};
+use crate::forge::formatter::{escape_link_url, escape_markdown, share_arrow};
+use chrono::Local;
+
+pub fn format_stats(stats: &DotaStats, config: &DotaConfig) -> String {
+    let now = Local::now();
+    let date = now.format("%Y\\-%m\\-%d").to_string();
+    let time = now.format("%H:%M:%S").to_string();
+
+    let mut name = format!("[{}]({})", escape_markdown(&stats.name), escape_link_url(&stats.profile_url));
+    if !stats.country.is_empty() {
+        name.push_str(&format!(" \\({}\\)", escape_markdown(&stats.country)));
+    }
+    let mmr = match stats.mmr_estimate {
+        Some(mmr) => format!("\\~{}", mmr),
+        None => "None".to_string(),
+    };
+
+    let mut message = format!(
+        "Dota 2 statistics for {}\n\
+        *Rank:* {}\n\
+        *MMR estimate:* {}\n\
+        *Matches:* {} \\({} W / {} L\\)\n\
+        *Win rate:* {}%\n\
+        *Last updated:* {} \\| {}",
+        name,
+        escape_markdown(&stats.rank),
+        mmr,
+        stats.wins + stats.losses,
+        stats.wins,
+        stats.losses,
+        escape_markdown(&format!("{:.2}", stats.win_rate())),
+        date,
+        time
+    );
+
+    message.push_str(&format_heroes(stats, config));
+    message.push_str(&format_recent(stats, config));
+    message
+}
+
+fn format_heroes(stats: &DotaStats, config: &DotaConfig) -> String {
+    if stats.heroes.is_empty() {
+        return String::new();
+    }
+
+    let bullet = if config.quote { ">•" } else { "•" };
+    let mut message = "\n\n*Most played heroes*".to_string();
+    for hero in &stats.heroes {
+        let win_rate = hero.wins as f64 / hero.games.max(1) as f64 * 100.0;
+        message.push_str(&format!(
+            "\n{} {}: {} games, {}%",
+            bullet,
+            escape_markdown(&hero.name),
+            hero.games,
+            escape_markdown(&format!("{:.0}", win_rate))
+        ));
+    }
+
+    message
+}
+
+/// Recent matches block, the win rate arrow compares it with the overall one.
+fn format_recent(stats: &DotaStats, config: &DotaConfig) -> String {
+    let (Some(win_rate), Some(kda)) = (stats.recent_win_rate(), stats.recent_kda()) else {
+        return String::new();
+    };
+
+    let results: Vec<&str> = stats.recent.iter().map(|m| if m.won { "W" } else { "L" }).collect();
+    let bullet = if config.quote { ">•" } else { "•" };
+
+    let mut message = format!(
+        "\n\n*Last {} matches:* {}\n\
+        {} Win rate: {}% {}\n\
+        {} KDA: {}",
+        stats.recent.len(),
+        results.join(" "),
+        bullet,
+        escape_markdown(&format!("{:.0}", win_rate)),
+        share_arrow(win_rate, Some(stats.win_rate())),
+        bullet,
+        escape_markdown(&format!("{:.2}", kda))
+    );
+
+    for game in stats.recent.iter().take(config.listed_matches) {
+        let played = chrono::DateTime::from_timestamp(game.start_time, 0)
+            .filter(|_| game.start_time > 0)
+            .map(|date| date.with_timezone(&Local).format("%m\\-%d ").to_string())
+            .unwrap_or_default();
+        message.push_str(&format!(
+            "\n{} {}{}: {}, {}/{}/{}",
+            bullet,
+            played,
+            escape_markdown(&game.hero),
+            if game.won { "win" } else { "loss" },
+            game.kills,
+            game.deaths,
+            game.assists
+        ));
+    }
+
+    message
+}
@@ -0,0 +1,43 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+pub mod analyzer;
+pub mod formatter;
+
+use crate::error::Result;
+use crate::services::{RenderContext, StatsService};
+use analyzer::DotaAnalyzer;
+use async_trait::async_trait;
+use client::DotaClient;
+use formatter::format_stats;
+use types::{DotaConfig, DotaStats};
+
+pub struct DotaService;
+
+#[async_trait]
+impl StatsService for DotaService {
+    type Config = DotaConfig;
+    type Stats = DotaStats;
+
+    fn name(&self) -> &'static str {
+        "dota"
+    }
+
+    fn message_id(&self, config: &DotaConfig) -> i64 {
+        config.message_id
+    }
+
+    async fn collect_stats(&self, config: &DotaConfig) -> Result<DotaStats> {
+        log::info!("starting dota analysis for account: {}", config.account_id);
+
+        let client = DotaClient::new(config)?;
+        let analyzer = DotaAnalyzer::new(client, config.account_id, &config.steam_vanity, config.heroes, config.recent_matches);
+        analyzer.analyze().await
+    }
+
+    fn render_message(&self, stats: &DotaStats, config: &DotaConfig, _context: &RenderContext) -> Result<String> {
+        Ok(format_stats(stats, config))
+    }
+}
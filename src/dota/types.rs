@@ -0,0 +1,226 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DotaConfig {
+    /// OpenDota key, "" = the free, rate limited tier.
+    #[serde(default)]
+    pub api_key: String,
+    pub message_id: i64,
+    /// Steam32 friend id, a Steam64 id works too. 0 = resolve `steam_vanity`.
+    #[serde(default)]
+    pub account_id: u64,
+    #[serde(default)]
+    pub steam_vanity: String,
+    #[serde(default)]
+    pub steam_api_key: String,
+    /// Most played heroes listed, 0 = no section.
+    #[serde(default = "default_heroes")]
+    pub heroes: usize,
+    /// Recent matches the KDA is computed over, OpenDota keeps the last 20.
+    #[serde(default = "default_recent_matches")]
+    pub recent_matches: usize,
+    #[serde(default = "default_listed_matches")]
+    pub listed_matches: usize,
+    pub quote: bool,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default = "default_steam_api_url")]
+    pub steam_api_url: String,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_heroes() -> usize {
+    5
+}
+
+fn default_recent_matches() -> usize {
+    20
+}
+
+fn default_listed_matches() -> usize {
+    5
+}
+
+fn default_api_url() -> String {
+    "https://api.opendota.com/api".to_string()
+}
+
+fn default_steam_api_url() -> String {
+    "https://api.steampowered.com".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+const STEAM64_BASE: u64 = 76561197960265728; // steam64 = base + steam32
+
+impl DotaConfig {
+    pub fn api_root(&self) -> &str {
+        self.api_url.trim_end_matches('/')
+    }
+
+    pub fn steam_api_root(&self) -> &str {
+        self.steam_api_url.trim_end_matches('/')
+    }
+}
+
+/// OpenDota works with steam32 ids, a steam64 one is converted.
+pub fn steam32(id: u64) -> u64 {
+    id.checked_sub(STEAM64_BASE).unwrap_or(id)
+}
+
+// hero ids used to be strings in some OpenDota responses
+fn number_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<u32, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(number) => number.as_u64().unwrap_or(0) as u32,
+        Value::String(text) => text.parse().unwrap_or(0),
+        _ => 0,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Profile {
+    pub account_id: u64,
+    #[serde(default)]
+    pub personaname: Option<String>,
+    #[serde(default)]
+    pub loccountrycode: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MmrEstimate {
+    #[serde(default)]
+    pub estimate: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerResponse {
+    #[serde(default)]
+    pub profile: Option<Profile>, // None = private or unknown account
+    #[serde(default)]
+    pub rank_tier: Option<u32>,
+    #[serde(default)]
+    pub leaderboard_rank: Option<u32>,
+    #[serde(default)]
+    pub mmr_estimate: Option<MmrEstimate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WinLoss {
+    pub win: u64,
+    pub lose: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerHero {
+    #[serde(deserialize_with = "number_or_string")]
+    pub hero_id: u32,
+    pub games: u64,
+    pub win: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Hero {
+    pub id: u32,
+    pub localized_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecentMatch {
+    pub player_slot: u32,
+    #[serde(default)]
+    pub radiant_win: Option<bool>,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    #[serde(deserialize_with = "number_or_string")]
+    pub hero_id: u32,
+    pub start_time: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VanityResponse {
+    pub response: VanityResult,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VanityResult {
+    pub success: u32, // 1 = found
+    #[serde(default)]
+    pub steamid: Option<String>,
+}
+
+/// Everything the analyzer needs, as OpenDota returned it.
+#[derive(Debug)]
+pub struct PlayerData {
+    pub player: PlayerResponse,
+    pub win_loss: WinLoss,
+    pub heroes: Vec<PlayerHero>, // most played first
+    pub hero_names: Vec<Hero>,
+    pub recent: Vec<RecentMatch>, // newest first
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HeroStats {
+    pub name: String,
+    pub games: u64,
+    pub wins: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DotaMatch {
+    pub hero: String,
+    pub won: bool,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub start_time: i64, // unix seconds
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DotaStats {
+    pub account_id: u64,
+    pub name: String,
+    pub country: String,
+    pub profile_url: String,
+    pub rank: String, // "Legend 3", "Immortal #512", "Uncalibrated"
+    pub mmr_estimate: Option<u32>,
+    pub wins: u64,
+    pub losses: u64,
+    pub heroes: Vec<HeroStats>,
+    pub recent: Vec<DotaMatch>,
+}
+
+impl DotaStats {
+    pub fn win_rate(&self) -> f64 {
+        let games = self.wins + self.losses;
+        if games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / games as f64 * 100.0
+    }
+
+    pub fn recent_win_rate(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let wins = self.recent.iter().filter(|m| m.won).count();
+        Some(wins as f64 / self.recent.len() as f64 * 100.0)
+    }
+
+    /// (kills + assists) / deaths over all recent matches.
+    pub fn recent_kda(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let kills: u32 = self.recent.iter().map(|m| m.kills + m.assists).sum();
+        let deaths: u32 = self.recent.iter().map(|m| m.deaths).sum();
+        Some(kills as f64 / deaths.max(1) as f64)
+    }
+}
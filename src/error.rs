@@ -0,0 +1,187 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, TeleStatsError>;
+
+#[derive(Debug, Error)]
+pub enum TeleStatsError {
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("authentication failed: {0}")]
+    Auth(String),
+    #[error("rate limited: {0}")]
+    RateLimited(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error("unexpected response: {0}")]
+    Api(String),
+    #[error("network error: {0}")]
+    Network(reqwest::Error),
+    #[error("failed to decode {0}")]
+    Decode(String),
+    #[error("telegram error: {0}")]
+    Telegram(#[from] teloxide::RequestError),
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("chart error: {0}")]
+    Chart(String),
+    #[error("git error: {0}")]
+    Git(String),
+    #[error("template error: {0}")]
+    Template(String),
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+}
+
+/// What the update loop should do with a failed service run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorAction {
+    /// Transient, worth another attempt in the same run.
+    Retry,
+    /// Nothing to post this time, no need to bother anyone.
+    Skip,
+    /// Needs a human, e.g. an expired token.
+    Alert,
+    /// The bot can't work until its config is fixed.
+    Abort,
+}
+
+impl TeleStatsError {
+    /// Maps a non-2xx forge response to the matching kind.
+    pub fn from_status(status: StatusCode, context: &str) -> Self {
+        let message = format!("{}: {}", context, status);
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => TeleStatsError::Auth(message),
+            StatusCode::NOT_FOUND => TeleStatsError::NotFound(message),
+            StatusCode::TOO_MANY_REQUESTS => TeleStatsError::RateLimited(message),
+            _ => TeleStatsError::Api(message),
+        }
+    }
+
+    pub fn action(&self) -> ErrorAction {
+        match self {
+            TeleStatsError::RateLimited(_) | TeleStatsError::Network(_) => ErrorAction::Retry,
+            TeleStatsError::Telegram(teloxide::RequestError::RetryAfter(_))
+            | TeleStatsError::Telegram(teloxide::RequestError::Network(_)) => ErrorAction::Retry,
+            TeleStatsError::NotFound(_) => ErrorAction::Skip,
+            TeleStatsError::Config(_) => ErrorAction::Abort,
+            _ => ErrorAction::Alert,
+        }
+    }
+}
+
+impl From<reqwest::Error> for TeleStatsError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            TeleStatsError::Decode(format!("response body: {}", e))
+        } else if let Some(status) = e.status() {
+            TeleStatsError::from_status(status, "request failed")
+        } else {
+            TeleStatsError::Network(e)
+        }
+    }
+}
+
+impl From<serde_yaml::Error> for TeleStatsError {
+    fn from(e: serde_yaml::Error) -> Self {
+        TeleStatsError::Config(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for TeleStatsError {
+    fn from(e: serde_json::Error) -> Self {
+        TeleStatsError::Decode(format!("json: {}", e))
+    }
+}
+
+impl From<base64::DecodeError> for TeleStatsError {
+    fn from(e: base64::DecodeError) -> Self {
+        TeleStatsError::Decode(format!("base64 content: {}", e))
+    }
+}
+
+impl From<reqwest::header::InvalidHeaderValue> for TeleStatsError {
+    fn from(e: reqwest::header::InvalidHeaderValue) -> Self {
+        TeleStatsError::Config(format!("token is not a valid header value: {}", e))
+    }
+}
+
+impl From<tokio::task::JoinError> for TeleStatsError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        TeleStatsError::Io(std::io::Error::other(e))
+    }
+}
+
+impl From<tera::Error> for TeleStatsError {
+    fn from(e: tera::Error) -> Self {
+        // tera keeps the useful part (line, unknown variable) in the source chain
+        let mut message = e.to_string();
+        let mut source = std::error::Error::source(&e);
+        while let Some(cause) = source {
+            message.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        TeleStatsError::Template(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use teloxide::types::Seconds;
+    use teloxide::{ApiError, RequestError};
+
+    fn reqwest_error() -> reqwest::Error {
+        reqwest::Client::new().get("not a url").build().unwrap_err()
+    }
+
+    #[test]
+    fn transient_errors_are_retried() {
+        assert_eq!(TeleStatsError::RateLimited("429".into()).action(), ErrorAction::Retry);
+        assert_eq!(TeleStatsError::Network(reqwest_error()).action(), ErrorAction::Retry);
+        assert_eq!(TeleStatsError::Telegram(RequestError::RetryAfter(Seconds::from_seconds(5))).action(), ErrorAction::Retry);
+    }
+
+    #[test]
+    fn missing_data_is_skipped() {
+        assert_eq!(TeleStatsError::NotFound("user".into()).action(), ErrorAction::Skip);
+    }
+
+    #[test]
+    fn broken_config_aborts() {
+        assert_eq!(TeleStatsError::Config("bad yaml".into()).action(), ErrorAction::Abort);
+    }
+
+    #[test]
+    fn everything_else_alerts() {
+        let errors = [
+            TeleStatsError::Auth("401".into()),
+            TeleStatsError::Api("500".into()),
+            TeleStatsError::Decode("json".into()),
+            TeleStatsError::Telegram(RequestError::Api(ApiError::MessageNotModified)),
+            TeleStatsError::Database(rusqlite::Error::InvalidQuery),
+            TeleStatsError::Io(std::io::Error::other("disk")),
+            TeleStatsError::Chart("font".into()),
+            TeleStatsError::Git("bad ref".into()),
+            TeleStatsError::Template("syntax".into()),
+            TeleStatsError::Unsupported("archive".into()),
+        ];
+        for error in errors {
+            assert_eq!(error.action(), ErrorAction::Alert, "{}", error);
+        }
+    }
+
+    #[test]
+    fn statuses_map_to_kinds() {
+        assert!(matches!(TeleStatsError::from_status(StatusCode::UNAUTHORIZED, ""), TeleStatsError::Auth(_)));
+        assert!(matches!(TeleStatsError::from_status(StatusCode::FORBIDDEN, ""), TeleStatsError::Auth(_)));
+        assert!(matches!(TeleStatsError::from_status(StatusCode::NOT_FOUND, ""), TeleStatsError::NotFound(_)));
+        assert!(matches!(TeleStatsError::from_status(StatusCode::TOO_MANY_REQUESTS, ""), TeleStatsError::RateLimited(_)));
+        assert!(matches!(TeleStatsError::from_status(StatusCode::BAD_GATEWAY, ""), TeleStatsError::Api(_)));
+    }
+}
@@ -0,0 +1,149 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::client::FaceitClient;
+use super::types::{stat_number, stat_text, FaceitStats, LifetimeResponse, MatchResult, MatchStatsItem, MatchStatsResponse, Player, PlayerGame};
+use crate::error::{Result, TeleStatsError};
+
+pub struct FaceitAnalyzer {
+    client: FaceitClient,
+    nickname: String,
+    game: String,
+    recent_matches: usize,
+}
+
+impl FaceitAnalyzer {
+    pub fn new(client: FaceitClient, nickname: &str, game: &str, recent_matches: usize) -> Self {
+        Self {
+            client,
+            nickname: nickname.to_string(),
+            game: game.to_string(),
+            recent_matches,
+        }
+    }
+
+    pub async fn analyze(&self) -> Result<FaceitStats> {
+        let player = self.client.get_player(&self.nickname, &self.game).await?;
+        player_game(&player, &self.game)?; // no point asking for stats of a game never played
+
+        let lifetime = self.client.get_lifetime_stats(&player.player_id, &self.game).await?;
+
+        // the trend is extra, a failing match list should not hide the rest
+        let recent = if self.recent_matches == 0 {
+            MatchStatsResponse::default()
+        } else {
+            match self.client.get_match_stats(&player.player_id, &self.game, self.recent_matches).await {
+                Ok(response) => response,
+                Err(e) => {
+                    log::warn!("failed to fetch recent faceit matches: {}", e);
+                    MatchStatsResponse::default()
+                }
+            }
+        };
+
+        build_stats(player, &self.game, lifetime, recent)
+    }
+}
+
+fn player_game<'a>(player: &'a Player, game: &str) -> Result<&'a PlayerGame> {
+    player.games
+        .get(game)
+        .ok_or_else(|| TeleStatsError::NotFound(format!("{} has not played {} on faceit", player.nickname, game)))
+}
+
+/// Turns Faceit's responses into the post's numbers, no requests involved.
+pub fn build_stats(player: Player, game_name: &str, lifetime: LifetimeResponse, recent: MatchStatsResponse) -> Result<FaceitStats> {
+    let game = player_game(&player, game_name)?;
+    let lifetime = lifetime.lifetime;
+
+    Ok(FaceitStats {
+        profile_url: player.faceit_url.replace("{lang}", "en"),
+        nickname: player.nickname.clone(),
+        country: player.country.to_uppercase(),
+        game: game_name.to_string(),
+        region: game.region.clone(),
+        elo: game.faceit_elo,
+        skill_level: game.skill_level,
+        matches: stat_number(&lifetime, "Matches") as u64,
+        win_rate: stat_number(&lifetime, "Win Rate %"),
+        kd_ratio: stat_number(&lifetime, "Average K/D Ratio"),
+        headshots: stat_number(&lifetime, "Average Headshots %"),
+        current_streak: stat_number(&lifetime, "Current Win Streak") as u64,
+        longest_streak: stat_number(&lifetime, "Longest Win Streak") as u64,
+        recent: recent.items.iter().map(match_result).collect(),
+    })
+}
+
+fn match_result(item: &MatchStatsItem) -> MatchResult {
+    let stats = &item.stats;
+    MatchResult {
+        map: stat_text(stats, "Map"),
+        score: stat_text(stats, "Score"),
+        won: stat_number(stats, "Result") >= 1.0,
+        kills: stat_number(stats, "Kills") as u32,
+        deaths: stat_number(stats, "Deaths") as u32,
+        finished_at: (stat_number(stats, "Match Finished At") / 1000.0) as i64, // sent in ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> T {
+        let path = format!("{}/tests/fixtures/faceit/{}", env!("CARGO_MANIFEST_DIR"), name);
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap()
+    }
+
+    fn stats(player: &str, game: &str) -> Result<FaceitStats> {
+        build_stats(fixture(player), game, fixture("lifetime.json"), fixture("matches.json"))
+    }
+
+    #[test]
+    fn builds_stats_from_faceit_responses() {
+        let stats = stats("player.json", "cs2").unwrap();
+
+        assert_eq!(stats.nickname, "s1mple");
+        assert_eq!(stats.country, "UA");
+        assert_eq!(stats.profile_url, "https://www.faceit.com/en/players/s1mple");
+        assert_eq!((stats.elo, stats.skill_level), (3412, 10));
+        assert_eq!(stats.region, "EU");
+        assert_eq!(stats.matches, 1523);
+        assert_eq!((stats.win_rate, stats.kd_ratio, stats.headshots), (58.0, 1.34, 41.0));
+        assert_eq!((stats.current_streak, stats.longest_streak), (2, 14));
+    }
+
+    #[test]
+    fn match_stats_take_strings_and_numbers() {
+        let stats = stats("player.json", "cs2").unwrap();
+
+        let results: Vec<(&str, &str, bool, u32, u32)> = stats.recent
+            .iter()
+            .map(|m| (m.map.as_str(), m.score.as_str(), m.won, m.kills, m.deaths))
+            .collect();
+        assert_eq!(results, [
+            ("de_mirage", "13 / 9", true, 27, 14),
+            ("de_inferno", "11 / 13", false, 18, 19),
+            ("de_dust2", "13 / 4", true, 15, 7),
+        ]);
+        assert_eq!(stats.recent[0].finished_at, 1735650000); // ms to seconds
+        assert_eq!(stats.recent[2].finished_at, 0); // not sent
+    }
+
+    #[test]
+    fn recent_kd_is_total_kills_over_total_deaths() {
+        let stats = stats("player.json", "cs2").unwrap();
+        assert_eq!(stats.recent_kd(), Some(60.0 / 40.0));
+        assert_eq!(stats.recent_win_rate(), Some(2.0 / 3.0 * 100.0));
+
+        let idle = build_stats(fixture("player.json"), "cs2", fixture("lifetime.json"), MatchStatsResponse::default()).unwrap();
+        assert_eq!(idle.recent_kd(), None);
+        assert_eq!(idle.recent_win_rate(), None);
+    }
+
+    #[test]
+    fn unplayed_game_is_not_found() {
+        assert!(matches!(stats("player_no_cs2.json", "cs2"), Err(TeleStatsError::NotFound(_))));
+    }
+}
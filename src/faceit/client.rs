@@ -0,0 +1,73 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{FaceitConfig, LifetimeResponse, MatchStatsResponse, Player};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::http::ForgeHttp;
+use reqwest::{Client, header};
+
+pub struct FaceitClient {
+    http: ForgeHttp, // same 429/retry handling as the forges
+    base_url: String,
+}
+
+impl FaceitClient {
+    pub fn new(config: &FaceitConfig) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            header::HeaderValue::from_str(&format!("Bearer {}", config.api_key))?,
+        );
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        Ok(Self {
+            http: ForgeHttp::new(client, config.max_retries),
+            base_url: config.api_root().to_string(),
+        })
+    }
+
+    pub async fn get_player(&self, nickname: &str, game: &str) -> Result<Player> {
+        let url = format!(
+            "{}/players?nickname={}&game={}",
+            self.base_url, urlencoding::encode(nickname), urlencoding::encode(game)
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch player"));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_lifetime_stats(&self, player_id: &str, game: &str) -> Result<LifetimeResponse> {
+        let url = format!("{}/players/{}/stats/{}", self.base_url, player_id, urlencoding::encode(game));
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch player stats"));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_match_stats(&self, player_id: &str, game: &str, limit: usize) -> Result<MatchStatsResponse> {
+        let url = format!(
+            "{}/players/{}/games/{}/stats?offset=0&limit={}",
+            self.base_url, player_id, urlencoding::encode(game), limit.clamp(1, 100) // newest first
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch matches"));
+        }
+
+        Ok(response.json().await?)
+    }
+}
@@ -0,0 +1,143 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{FaceitConfig, FaceitStats};
+use crate::forge::formatter::{escape_link_url, escape_markdown, share_arrow};
+use chrono::Local;
+
+pub fn format_stats(stats: &FaceitStats, config: &FaceitConfig) -> String {
+    let now = Local::now();
+    let date = now.format("%Y\\-%m\\-%d").to_string();
+    let time = now.format("%H:%M:%S").to_string();
+
+    let mut nickname = match stats.profile_url.as_str() {
+        "" => escape_markdown(&stats.nickname),
+        url => format!("[{}]({})", escape_markdown(&stats.nickname), escape_link_url(url)),
+    };
+    if !stats.country.is_empty() {
+        nickname.push_str(&format!(" \\({}\\)", escape_markdown(&stats.country)));
+    }
+
+    let mut message = format!(
+        "Faceit statistics for {} in {}\n\
+        *ELO:* {} \\(level {}\\)\n\
+        *Region:* {}\n\
+        *Matches:* {}\n\
+        *Win rate:* {}%\n\
+        *K/D ratio:* {}\n\
+        *Headshots:* {}%\n\
+        *Win streak:* {} \\(longest {}\\)\n\
+        *Last updated:* {} \\| {}",
+        nickname,
+        escape_markdown(&stats.game.to_uppercase()),
+        stats.elo,
+        stats.skill_level,
+        escape_markdown(if stats.region.is_empty() { "None" } else { &stats.region }),
+        stats.matches,
+        escape_markdown(&format!("{:.0}", stats.win_rate)),
+        escape_markdown(&format!("{:.2}", stats.kd_ratio)),
+        escape_markdown(&format!("{:.0}", stats.headshots)),
+        stats.current_streak,
+        stats.longest_streak,
+        date,
+        time
+    );
+
+    message.push_str(&format_recent(stats, config));
+    message
+}
+
+/// Last-N-matches block, arrows compare it with the lifetime numbers.
+fn format_recent(stats: &FaceitStats, config: &FaceitConfig) -> String {
+    let (Some(win_rate), Some(kd_ratio)) = (stats.recent_win_rate(), stats.recent_kd()) else {
+        return String::new();
+    };
+
+    let results: Vec<&str> = stats.recent.iter().map(|m| if m.won { "W" } else { "L" }).collect();
+    let bullet = if config.quote { ">•" } else { "•" };
+
+    let mut message = format!(
+        "\n\n*Last {} matches:* {}\n\
+        {} Win rate: {}% {}\n\
+        {} K/D ratio: {} {}",
+        stats.recent.len(),
+        results.join(" "),
+        bullet,
+        escape_markdown(&format!("{:.0}", win_rate)),
+        share_arrow(win_rate, Some(stats.win_rate)),
+        bullet,
+        escape_markdown(&format!("{:.2}", kd_ratio)),
+        share_arrow(kd_ratio, Some(stats.kd_ratio))
+    );
+
+    for result in stats.recent.iter().take(config.listed_matches) {
+        let played = chrono::DateTime::from_timestamp(result.finished_at, 0)
+            .filter(|_| result.finished_at > 0)
+            .map(|date| date.with_timezone(&Local).format("%m\\-%d ").to_string())
+            .unwrap_or_default();
+        message.push_str(&format!(
+            "\n{} {}{} {}: {}, {}/{}",
+            bullet,
+            played,
+            escape_markdown(&result.map),
+            escape_markdown(&result.score),
+            if result.won { "win" } else { "loss" },
+            result.kills,
+            result.deaths
+        ));
+    }
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::faceit::types::MatchResult;
+
+    fn config() -> FaceitConfig {
+        FaceitConfig {
+            api_key: String::new(),
+            message_id: 0,
+            nickname: "the_one.x".to_string(),
+            game: "cs2".to_string(),
+            recent_matches: 20,
+            listed_matches: 1,
+            quote: false,
+            api_url: String::new(),
+            max_retries: 0,
+        }
+    }
+
+    fn stats() -> FaceitStats {
+        FaceitStats {
+            nickname: "the_one.x".to_string(),
+            country: "UA".to_string(),
+            profile_url: "https://www.faceit.com/en/players/the_one.x(1)".to_string(),
+            game: "cs2".to_string(),
+            region: "EU".to_string(),
+            elo: 2001,
+            skill_level: 10,
+            matches: 100,
+            win_rate: 51.0,
+            kd_ratio: 1.05,
+            headshots: 44.0,
+            current_streak: 1,
+            longest_streak: 7,
+            recent: vec![
+                MatchResult { map: "de_dust2".to_string(), score: "13 / 11".to_string(), won: true, kills: 20, deaths: 10, finished_at: 0 },
+                MatchResult { map: "de_nuke".to_string(), score: "2 / 13".to_string(), won: false, kills: 5, deaths: 15, finished_at: 0 },
+            ],
+        }
+    }
+
+    #[test]
+    fn escapes_markdown_in_names_and_numbers() {
+        let message = format_stats(&stats(), &config());
+        assert!(message.starts_with("Faceit statistics for [the\\_one\\.x](https://www.faceit.com/en/players/the_one.x(1\\)) \\(UA\\) in CS2\n"), "{}", message);
+        assert!(message.contains("*K/D ratio:* 1\\.05\n"));
+        assert!(message.contains("*Last 2 matches:* W L\n"));
+        assert!(message.contains("• K/D ratio: 1\\.00 ▼"));
+        assert!(message.ends_with("\n• de\\_dust2 13 / 11: win, 20/10"), "{}", message);
+    }
+}
@@ -0,0 +1,43 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+pub mod analyzer;
+pub mod formatter;
+
+use crate::error::Result;
+use crate::services::{RenderContext, StatsService};
+use analyzer::FaceitAnalyzer;
+use async_trait::async_trait;
+use client::FaceitClient;
+use formatter::format_stats;
+use types::{FaceitConfig, FaceitStats};
+
+pub struct FaceitService;
+
+#[async_trait]
+impl StatsService for FaceitService {
+    type Config = FaceitConfig;
+    type Stats = FaceitStats;
+
+    fn name(&self) -> &'static str {
+        "faceit"
+    }
+
+    fn message_id(&self, config: &FaceitConfig) -> i64 {
+        config.message_id
+    }
+
+    async fn collect_stats(&self, config: &FaceitConfig) -> Result<FaceitStats> {
+        log::info!("starting faceit analysis for player: {}", config.nickname);
+
+        let client = FaceitClient::new(config)?;
+        let analyzer = FaceitAnalyzer::new(client, &config.nickname, &config.game, config.recent_matches);
+        analyzer.analyze().await
+    }
+
+    fn render_message(&self, stats: &FaceitStats, config: &FaceitConfig, _context: &RenderContext) -> Result<String> {
+        Ok(format_stats(stats, config))
+    }
+}
@@ -0,0 +1,158 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FaceitConfig {
+    /// Server-side key from developers.faceit.com.
+    pub api_key: String,
+    pub message_id: i64,
+    pub nickname: String,
+    #[serde(default = "default_game")]
+    pub game: String,
+    /// Matches the trend is computed over, the API returns at most 100.
+    #[serde(default = "default_recent_matches")]
+    pub recent_matches: usize,
+    /// Of those, how many are listed one by one.
+    #[serde(default = "default_listed_matches")]
+    pub listed_matches: usize,
+    pub quote: bool,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+}
+
+fn default_game() -> String {
+    "cs2".to_string()
+}
+
+fn default_recent_matches() -> usize {
+    20
+}
+
+fn default_listed_matches() -> usize {
+    5
+}
+
+fn default_api_url() -> String {
+    "https://open.faceit.com/data/v4".to_string()
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+impl FaceitConfig {
+    pub fn api_root(&self) -> &str {
+        self.api_url.trim_end_matches('/')
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerGame {
+    pub skill_level: u32,
+    pub faceit_elo: u32,
+    #[serde(default)]
+    pub region: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Player {
+    pub player_id: String,
+    pub nickname: String,
+    #[serde(default)]
+    pub country: String,
+    #[serde(default)]
+    pub faceit_url: String, // has a "{lang}" placeholder
+    #[serde(default)]
+    pub games: HashMap<String, PlayerGame>,
+}
+
+/// `lifetime` of /players/{id}/stats/{game}, numbers come as strings.
+#[derive(Debug, Deserialize)]
+pub struct LifetimeResponse {
+    #[serde(default)]
+    pub lifetime: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MatchStatsItem {
+    #[serde(default)]
+    pub stats: HashMap<String, Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct MatchStatsResponse {
+    #[serde(default)]
+    pub items: Vec<MatchStatsItem>,
+}
+
+/// Reads a stat that may be a number or a numeric string ("1.12", "53").
+pub fn stat_number(stats: &HashMap<String, Value>, key: &str) -> f64 {
+    match stats.get(key) {
+        Some(Value::Number(number)) => number.as_f64().unwrap_or(0.0),
+        Some(Value::String(text)) => text.trim().parse().unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
+
+pub fn stat_text(stats: &HashMap<String, Value>, key: &str) -> String {
+    match stats.get(key) {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Number(number)) => number.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchResult {
+    pub map: String,
+    pub score: String, // "13 / 10"
+    pub won: bool,
+    pub kills: u32,
+    pub deaths: u32,
+    pub finished_at: i64, // unix seconds, 0 = unknown
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FaceitStats {
+    pub nickname: String,
+    pub country: String,
+    pub profile_url: String,
+    pub game: String,
+    pub region: String,
+    pub elo: u32,
+    pub skill_level: u32,
+    pub matches: u64,
+    pub win_rate: f64, // percent
+    pub kd_ratio: f64,
+    pub headshots: f64, // percent
+    pub current_streak: u64,
+    pub longest_streak: u64,
+    pub recent: Vec<MatchResult>, // newest first
+}
+
+impl FaceitStats {
+    /// Win rate over `recent`, None without matches.
+    pub fn recent_win_rate(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let wins = self.recent.iter().filter(|m| m.won).count();
+        Some(wins as f64 / self.recent.len() as f64 * 100.0)
+    }
+
+    /// Kills over deaths of `recent` as a whole, not the mean of per-match ratios.
+    pub fn recent_kd(&self) -> Option<f64> {
+        if self.recent.is_empty() {
+            return None;
+        }
+        let kills: u32 = self.recent.iter().map(|m| m.kills).sum();
+        let deaths: u32 = self.recent.iter().map(|m| m.deaths).sum();
+        Some(kills as f64 / deaths.max(1) as f64)
+    }
+}
@@ -0,0 +1,174 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::{ErrorAction, TeleStatsError};
+use crate::logs;
+use serde::Deserialize;
+use std::io::IsTerminal;
+use teloxide::prelude::*;
+use teloxide::types::ChatId;
+use tokio::time::Duration;
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureMode {
+    /// Log the error and go on with the next service.
+    #[default]
+    Continue,
+    /// Run the service again up to `retries` times, then continue.
+    Retry,
+    /// Stop the bot with `exit_code`, for supervisors that restart it.
+    Exit,
+    /// Send the error to `admin_chat_id` and continue.
+    Notify,
+    /// Ask on the terminal, only honored when stdin is a TTY.
+    Prompt,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FailurePolicy {
+    #[serde(default)]
+    pub mode: FailureMode,
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    #[serde(default = "default_retry_delay")]
+    pub retry_delay: u64, // seconds
+    #[serde(default = "default_exit_code")]
+    pub exit_code: i32,
+    #[serde(default)]
+    pub admin_chat_id: i64,
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+fn default_retry_delay() -> u64 {
+    30
+}
+
+fn default_exit_code() -> i32 {
+    1
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        Self {
+            mode: FailureMode::default(),
+            retries: default_retries(),
+            retry_delay: default_retry_delay(),
+            exit_code: default_exit_code(),
+            admin_chat_id: 0,
+        }
+    }
+}
+
+impl FailurePolicy {
+    /// How many more runs a service gets after failing with `error`.
+    pub fn retries_for(&self, error: &TeleStatsError) -> u32 {
+        match error.action() {
+            ErrorAction::Skip | ErrorAction::Abort => 0,
+            _ if self.mode == FailureMode::Retry => self.retries,
+            ErrorAction::Retry => 1, // transient, one more try is always worth it
+            ErrorAction::Alert => 0,
+        }
+    }
+
+    pub fn retry_delay(&self) -> Duration {
+        Duration::from_secs(self.retry_delay)
+    }
+
+    /// Applies the policy to a service that failed for good. Never blocks
+    /// the runtime: the prompt runs on the blocking pool.
+    pub async fn handle(&self, bot: &Bot, service: &str, error: &str, action: ErrorAction) {
+        logs::update_failed(service, error);
+
+        // alerts need a human whatever the mode, notify sends everything
+        if action == ErrorAction::Alert || self.mode == FailureMode::Notify {
+            self.notify(bot, service, error).await;
+        }
+
+        match self.mode {
+            FailureMode::Continue | FailureMode::Retry | FailureMode::Notify => {}
+            FailureMode::Exit => {
+                log::error!("shutting down with exit code {} by failure policy", self.exit_code);
+                std::process::exit(self.exit_code);
+            }
+            FailureMode::Prompt => {
+                if !std::io::stdin().is_terminal() {
+                    log::warn!("failure policy is prompt but stdin is not a tty, continuing");
+                    return;
+                }
+                let keep_running = tokio::task::spawn_blocking(logs::prompt_continue)
+                    .await
+                    .unwrap_or(true);
+                if !keep_running {
+                    log::info!("shutting down bot by user request");
+                    std::process::exit(0);
+                }
+            }
+        }
+    }
+
+    /// A service whose config is broken: running on would only fail the
+    /// same way every update, so the bot stops in every mode.
+    pub async fn abort(&self, bot: &Bot, service: &str, error: &str) {
+        logs::update_failed(service, error);
+        self.notify(bot, service, error).await;
+        log::error!(
+            "{} service config is invalid, fix src/configs/ and restart; shutting down with exit code {}",
+            service, self.exit_code
+        );
+        std::process::exit(self.exit_code);
+    }
+
+    async fn notify(&self, bot: &Bot, service: &str, error: &str) {
+        if self.admin_chat_id == 0 {
+            if self.mode == FailureMode::Notify {
+                log::warn!("failure policy is notify but admin_chat_id is not set");
+            }
+            return;
+        }
+        let text = format!("{} service update failed: {}", service, error);
+        if let Err(e) = bot.send_message(ChatId(self.admin_chat_id), text).await {
+            log::error!("failed to notify admin: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(mode: FailureMode) -> FailurePolicy {
+        FailurePolicy { mode, retries: 3, ..FailurePolicy::default() }
+    }
+
+    #[test]
+    fn transient_errors_get_one_retry_by_default() {
+        let error = TeleStatsError::RateLimited("429".into());
+        assert_eq!(policy(FailureMode::Continue).retries_for(&error), 1);
+        assert_eq!(policy(FailureMode::Notify).retries_for(&error), 1);
+    }
+
+    #[test]
+    fn retry_mode_retries_alerts_and_transient_errors() {
+        let policy = policy(FailureMode::Retry);
+        assert_eq!(policy.retries_for(&TeleStatsError::RateLimited("429".into())), 3);
+        assert_eq!(policy.retries_for(&TeleStatsError::Api("500".into())), 3);
+    }
+
+    #[test]
+    fn alerts_are_not_retried_outside_retry_mode() {
+        assert_eq!(policy(FailureMode::Continue).retries_for(&TeleStatsError::Auth("401".into())), 0);
+        assert_eq!(policy(FailureMode::Exit).retries_for(&TeleStatsError::Auth("401".into())), 0);
+    }
+
+    #[test]
+    fn skips_and_aborts_are_never_retried() {
+        for mode in [FailureMode::Continue, FailureMode::Retry, FailureMode::Notify] {
+            assert_eq!(policy(mode).retries_for(&TeleStatsError::NotFound("user".into())), 0);
+            assert_eq!(policy(mode).retries_for(&TeleStatsError::Config("bad yaml".into())), 0);
+        }
+    }
+}
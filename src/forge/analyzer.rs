@@ -0,0 +1,363 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::archive;
+use super::cache::{CachedBlob, CachedRepo, RepoCache};
+use super::client::ForgeClient;
+use super::counter::{self, LineCounts};
+use super::detect::{self, Candidate};
+use super::types::{rank_languages, AnalysisMode, AnalyzerSettings, ForgeRepo, ForgeStats, RepoStats, TreeEntry};
+use crate::error::Result;
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+enum RepoOutcome {
+    Analyzed { repo: Box<ForgeRepo>, stats: CachedRepo, skipped_files: usize },
+    Failed,
+    Empty, // no default branch or no files, nothing to count
+}
+
+pub struct ForgeAnalyzer<C: ForgeClient> {
+    client: C,
+    settings: AnalyzerSettings,
+    requests: Semaphore, // shared by every repo analyzed in parallel
+}
+
+impl<C: ForgeClient> ForgeAnalyzer<C> {
+    pub fn new(client: C, settings: AnalyzerSettings) -> Self {
+        let requests = Semaphore::new(settings.max_requests.max(1));
+        Self { client, settings, requests }
+    }
+
+    async fn permit(&self) -> SemaphorePermit<'_> {
+        self.requests
+            .acquire()
+            .await
+            .expect("request semaphore is never closed")
+    }
+
+    pub async fn analyze(&self) -> Result<ForgeStats> {
+        let repos = {
+            let _permit = self.permit().await;
+            self.client
+                .list_repos(&self.settings.username, self.settings.private_repo)
+                .await?
+        };
+
+        let previous = self.settings.cache_path
+            .as_deref()
+            .map(|path| RepoCache::load(path, &self.settings.languages, &self.settings.fingerprint()))
+            .unwrap_or_default();
+        let mut cache = RepoCache::new(self.settings.languages.clone(), self.settings.fingerprint());
+
+        let mut language_counts: HashMap<String, LineCounts> = HashMap::new();
+        let mut line_counts = LineCounts::default();
+        let mut public_repos = 0;
+        let mut last_activity = String::new();
+
+        for repo in &repos {
+            if !repo.private {
+                public_repos += 1;
+            }
+
+            if last_activity.is_empty() || repo.last_activity > last_activity {
+                last_activity = repo.last_activity.clone();
+            }
+        }
+
+        let previous = &previous;
+        let analyzed: Vec<RepoOutcome> = stream::iter(repos.iter().cloned())
+            .map(|repo| async move {
+                let Some(branch) = repo.default_branch.as_deref() else {
+                    return RepoOutcome::Empty;
+                };
+                match self.analyze_repository(&repo, branch, previous.repos.get(&repo.id)).await {
+                    Ok(Some((stats, skipped_files))) => RepoOutcome::Analyzed { repo: Box::new(repo), stats, skipped_files },
+                    Ok(None) => RepoOutcome::Empty,
+                    Err(e) => {
+                        log::warn!("failed to analyze repo {}: {}", repo.name, e);
+                        RepoOutcome::Failed
+                    }
+                }
+            })
+            .buffer_unordered(self.settings.parallel_repos.max(1))
+            .collect()
+            .await;
+
+        let mut skipped_files = 0;
+        let mut skipped_repos = 0;
+        let mut repo_results = Vec::new();
+        for outcome in analyzed {
+            let (repo, repo_stats, skipped) = match outcome {
+                RepoOutcome::Analyzed { repo, stats, skipped_files } => (repo, stats, skipped_files),
+                RepoOutcome::Failed => {
+                    skipped_repos += 1;
+                    continue;
+                }
+                RepoOutcome::Empty => continue,
+            };
+
+            for (lang, counts) in &repo_stats.languages {
+                *language_counts.entry(lang.clone()).or_default() += *counts;
+                line_counts += *counts;
+            }
+            skipped_files += skipped;
+            let id = repo.id.clone();
+            repo_results.push(RepoStats::new(*repo, repo_stats.languages.clone()));
+            cache.repos.insert(id, repo_stats);
+        }
+
+        if skipped_files > 0 || skipped_repos > 0 {
+            log::warn!(
+                "analysis finished with incomplete data: {} files and {} repos skipped",
+                skipped_files,
+                skipped_repos
+            );
+        }
+
+        if let Some(path) = &self.settings.cache_path {
+            if let Err(e) = cache.save(path) {
+                log::warn!("failed to save cache {}: {}", path.display(), e);
+            }
+        }
+
+        // "lines" from here on are code lines, comments and blanks are kept apart
+        let total_lines = line_counts.code;
+        let language_lines: HashMap<String, usize> = language_counts
+            .iter()
+            .map(|(lang, counts)| (lang.clone(), counts.code))
+            .collect();
+        let mut language_stats = rank_languages(&language_lines, total_lines);
+
+        let favorite_language = language_stats
+            .first()
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| "None".to_string());
+
+        let total_languages = language_stats.len();
+
+        if language_stats.len() > self.settings.max_langs {
+            language_stats.truncate(self.settings.max_langs);
+        }
+
+        Ok(ForgeStats {
+            total_lines,
+            language_stats,
+            language_lines,
+            line_counts,
+            language_counts,
+            total_languages,
+            favorite_language,
+            total_repos: repos.len(),
+            public_repos,
+            last_activity,
+            skipped_files,
+            skipped_repos,
+            repos: repo_results,
+        })
+    }
+
+    /// Counts of one repo plus how many of its files could not be read,
+    /// None if the branch has no files at all.
+    async fn analyze_repository(&self, repo: &ForgeRepo, branch: &str, cached: Option<&CachedRepo>) -> Result<Option<(CachedRepo, usize)>> {
+        let head_sha = if self.settings.cache_path.is_some() {
+            let _permit = self.permit().await;
+            match self.client.head_commit(repo, branch).await {
+                Ok(sha) => sha,
+                Err(e) => {
+                    log::warn!("failed to resolve head of {}: {}", repo.name, e);
+                    String::new()
+                }
+            }
+        } else {
+            String::new()
+        };
+
+        if let Some(cached) = cached {
+            if !head_sha.is_empty() && cached.head_sha == head_sha {
+                log::info!("repo unchanged, skipping: {}", repo.name);
+                return Ok(Some((cached.clone(), 0)));
+            }
+        }
+
+        match self.settings.mode {
+            AnalysisMode::Api => self.analyze_files(repo, branch, head_sha, cached).await,
+            AnalysisMode::Archive => {
+                log::info!("downloading archive: {}", repo.name);
+                let _permit = self.permit().await; // held until the tarball is fully read
+                let stream = self.client.fetch_archive(repo, branch).await?;
+                let languages = archive::count_archive(stream, self.settings.clone()).await?;
+                let repo_stats = CachedRepo {
+                    head_sha,
+                    languages,
+                    blobs: HashMap::new(),
+                };
+                Ok(Some((repo_stats, 0)))
+            }
+        }
+    }
+
+    async fn analyze_files(&self, repo: &ForgeRepo, branch: &str, head_sha: String, cached: Option<&CachedRepo>) -> Result<Option<(CachedRepo, usize)>> {
+        let tree = {
+            let _permit = self.permit().await;
+            self.client.list_tree(repo, branch).await?
+        };
+        if tree.is_empty() {
+            log::info!("repo has no files, skipping: {}", repo.name);
+            return Ok(None);
+        }
+        let mut analyzed = CachedRepo {
+            head_sha,
+            ..CachedRepo::default()
+        };
+
+        let gitattributes = match tree.iter().find(|item| item.path == ".gitattributes") {
+            Some(item) => {
+                let _permit = self.permit().await;
+                match self.client.fetch_file(repo, &item.path, branch).await {
+                    Ok(content) => Some(content),
+                    Err(e) => {
+                        log::warn!("failed to fetch .gitattributes of {}: {}", repo.name, e);
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        let filter = self.settings.filter.for_repo(gitattributes.as_deref());
+
+        let files: Vec<(TreeEntry, Candidate)> = tree
+            .into_iter()
+            .filter(|item| filter.counts(&item.path))
+            .map(|item| {
+                let candidate = self.settings.detect(&item.path);
+                (item, candidate)
+            })
+            .filter(|(_, candidate)| !matches!(candidate, Candidate::NotCode))
+            .collect();
+
+        // the semaphore caps in-flight requests, the buffer only avoids
+        // spawning a future per file of a huge monorepo up front
+        let counted: Vec<(TreeEntry, Option<(String, LineCounts)>)> = stream::iter(files)
+            .map(|(item, candidate)| async move {
+                let unchanged = cached
+                    .and_then(|c| c.blobs.get(&item.path))
+                    .filter(|blob| !item.sha.is_empty() && blob.sha == item.sha);
+
+                let counted = if let Some(blob) = unchanged {
+                    Some((blob.language.clone(), blob.counts))
+                } else {
+                    let _permit = self.permit().await;
+                    log::info!("reading file: {}", item.path);
+                    match self.client.fetch_file(repo, &item.path, branch).await {
+                        Ok(content) => Some(match detect::resolve(&item.path, candidate, &content) {
+                            Some(lang_name) => {
+                                let counts = counter::count_lines(&content, counter::syntax_for(&lang_name));
+                                (lang_name, counts)
+                            }
+                            None => (String::new(), LineCounts::default()), // remembered as not code
+                        }),
+                        Err(e) => {
+                            log::warn!("failed to fetch file {}: {}", item.path, e);
+                            None
+                        }
+                    }
+                };
+
+                (item, counted)
+            })
+            .buffer_unordered(self.settings.max_requests.max(1))
+            .collect()
+            .await;
+
+        let mut skipped = 0;
+        for (item, counted) in counted {
+            let Some((lang_name, counts)) = counted else {
+                analyzed.head_sha.clear(); // incomplete, look at it again next run
+                skipped += 1;
+                continue;
+            };
+
+            if !lang_name.is_empty() {
+                *analyzed.languages.entry(lang_name.clone()).or_default() += counts;
+            }
+            analyzed.blobs.insert(item.path, CachedBlob {
+                sha: item.sha,
+                language: lang_name, // empty = not code
+                counts,
+            });
+        }
+
+        Ok(Some((analyzed, skipped)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TeleStatsError;
+    use crate::forge::filter::{PathFilter, PathRules};
+    use async_trait::async_trait;
+
+    /// One repo per entry, the tree is the list of files with their content.
+    struct StubClient(Vec<(&'static str, Vec<(&'static str, &'static str)>)>);
+
+    #[async_trait]
+    impl ForgeClient for StubClient {
+        async fn list_repos(&self, _username: &str, _include_private: bool) -> Result<Vec<ForgeRepo>> {
+            Ok(self.0.iter().map(|(name, _)| ForgeRepo {
+                id: name.to_string(),
+                name: name.to_string(),
+                default_branch: Some("main".to_string()),
+                private: false,
+                last_activity: String::new(),
+                url: None,
+                remote: None,
+            }).collect())
+        }
+
+        async fn head_commit(&self, _repo: &ForgeRepo, _branch: &str) -> Result<String> {
+            Err(TeleStatsError::Unsupported("head".to_string()))
+        }
+
+        async fn list_tree(&self, repo: &ForgeRepo, _branch: &str) -> Result<Vec<TreeEntry>> {
+            let (_, files) = self.0.iter().find(|(name, _)| *name == repo.id).unwrap();
+            Ok(files.iter().map(|(path, _)| TreeEntry { path: path.to_string(), sha: String::new() }).collect())
+        }
+
+        async fn fetch_file(&self, repo: &ForgeRepo, path: &str, _branch: &str) -> Result<String> {
+            let (_, files) = self.0.iter().find(|(name, _)| *name == repo.id).unwrap();
+            Ok(files.iter().find(|(p, _)| *p == path).unwrap().1.to_string())
+        }
+    }
+
+    fn settings() -> AnalyzerSettings {
+        AnalyzerSettings {
+            username: "someone".to_string(),
+            private_repo: false,
+            max_langs: 10,
+            mode: AnalysisMode::Api,
+            cache_path: None,
+            max_requests: 4,
+            parallel_repos: 2,
+            languages: HashMap::from([("rs".to_string(), "Rust".to_string())]),
+            filenames: HashMap::new(),
+            filter: PathFilter::new(&PathRules::default()).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_repos_are_not_skipped() {
+        let client = StubClient(vec![
+            ("empty", vec![]),
+            ("code", vec![("main.rs", "fn main() {}\n")]),
+        ]);
+        let stats = ForgeAnalyzer::new(client, settings()).analyze().await.unwrap();
+
+        assert_eq!(stats.skipped_repos, 0);
+        assert_eq!(stats.total_repos, 2);
+        assert_eq!(stats.repos.len(), 1);
+        assert_eq!(stats.language_lines.get("Rust"), Some(&1));
+    }
+}
@@ -0,0 +1,81 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::client::ArchiveStream;
+use super::counter::{self, LineCounts};
+use super::detect::{self, Candidate};
+use super::types::AnalyzerSettings;
+use crate::error::Result;
+use flate2::read::GzDecoder;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+
+/// Wraps a streaming HTTP body so it can be fed to `count_archive`.
+pub fn response_stream(response: reqwest::Response) -> ArchiveStream {
+    let body = response.bytes_stream().map_err(io::Error::other);
+    Box::pin(StreamReader::new(body))
+}
+
+/// Counts lines per language in a `.tar.gz` snapshot while it downloads,
+/// nothing is unpacked to disk.
+pub async fn count_archive(stream: ArchiveStream, settings: AnalyzerSettings) -> Result<HashMap<String, LineCounts>> {
+    let language_lines = tokio::task::spawn_blocking(move || {
+        count_tarball(SyncIoBridge::new(stream), &settings)
+    })
+    .await??;
+
+    Ok(language_lines)
+}
+
+fn count_tarball<R: Read>(reader: R, settings: &AnalyzerSettings) -> io::Result<HashMap<String, LineCounts>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut counted: Vec<(String, String, LineCounts)> = Vec::new();
+    let mut gitattributes = None; // can come anywhere in the stream, so filter at the end
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        // both forges wrap the tree into a single `<repo>-<sha>/` directory
+        let relative: PathBuf = entry.path()?.components().skip(1).collect();
+        let Some(path) = relative.to_str().map(str::to_string) else {
+            continue;
+        };
+
+        if path == ".gitattributes" {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            gitattributes = Some(content);
+            continue;
+        }
+
+        let candidate = settings.detect(&path);
+        if matches!(candidate, Candidate::NotCode) {
+            continue;
+        }
+
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        let content = String::from_utf8_lossy(&content);
+        let Some(lang_name) = detect::resolve(&path, candidate, &content) else {
+            continue;
+        };
+        let counts = counter::count_lines(&content, counter::syntax_for(&lang_name));
+        counted.push((path, lang_name, counts));
+    }
+
+    let filter = settings.filter.for_repo(gitattributes.as_deref());
+    let mut language_lines: HashMap<String, LineCounts> = HashMap::new();
+    for (path, lang_name, counts) in counted {
+        if filter.counts(&path) {
+            *language_lines.entry(lang_name).or_default() += counts;
+        }
+    }
+
+    Ok(language_lines)
+}
@@ -0,0 +1,85 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::counter::LineCounts;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CachedBlob {
+    pub sha: String,
+    pub language: String,
+    pub counts: LineCounts,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CachedRepo {
+    /// Head of the default branch the counts belong to, empty if the last
+    /// run could not read every file and the repo has to be looked at again.
+    pub head_sha: String,
+    pub languages: HashMap<String, LineCounts>,
+    #[serde(default)]
+    pub blobs: HashMap<String, CachedBlob>, // path -> blob, api mode only
+}
+
+/// Bumped whenever what a cached count means changes.
+const CACHE_VERSION: u32 = 2;
+
+/// Per-repo results of the previous runs, stored as JSON next to the bot.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RepoCache {
+    #[serde(default)]
+    pub version: u32,
+    /// Extension map the counts were made with, any change invalidates them.
+    #[serde(default)]
+    pub languages: HashMap<String, String>,
+    /// `AnalyzerSettings::fingerprint` of the rules the counts were made with.
+    #[serde(default)]
+    pub rules: String,
+    #[serde(default)]
+    pub repos: HashMap<String, CachedRepo>,
+}
+
+impl RepoCache {
+    pub fn new(languages: HashMap<String, String>, rules: String) -> Self {
+        RepoCache {
+            version: CACHE_VERSION,
+            languages,
+            rules,
+            repos: HashMap::new(),
+        }
+    }
+
+    pub fn load(path: &Path, languages: &HashMap<String, String>, rules: &str) -> Self {
+        let cache = match fs::read_to_string(path) {
+            Ok(content) => match serde_json::from_str::<RepoCache>(&content) {
+                Ok(cache) => cache,
+                Err(e) => {
+                    log::warn!("ignoring broken cache {}: {}", path.display(), e);
+                    RepoCache::default()
+                }
+            },
+            Err(_) => RepoCache::default(),
+        };
+
+        if cache.version != CACHE_VERSION || &cache.languages != languages || cache.rules != rules {
+            if !cache.repos.is_empty() {
+                log::info!("languages, path rules or cache format changed, dropping cache {}", path.display());
+            }
+            return RepoCache::new(languages.clone(), rules.to_string());
+        }
+
+        cache
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
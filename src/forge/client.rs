@@ -0,0 +1,30 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{ForgeRepo, TreeEntry};
+use crate::error::{Result, TeleStatsError};
+use async_trait::async_trait;
+use std::pin::Pin;
+use tokio::io::AsyncRead;
+
+/// Body of a `.tar.gz` repository snapshot, read as it arrives.
+pub type ArchiveStream = Pin<Box<dyn AsyncRead + Send>>;
+
+/// Minimal API surface a forge has to expose to be analyzed.
+#[async_trait]
+pub trait ForgeClient: Send + Sync {
+    async fn list_repos(&self, username: &str, include_private: bool) -> Result<Vec<ForgeRepo>>;
+
+    /// Sha of the commit the branch currently points at.
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String>;
+
+    /// Blobs only, with paths relative to the repository root.
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>>;
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String>;
+
+    /// Gzipped tarball of the branch, used by `analysis_mode: archive`.
+    async fn fetch_archive(&self, _repo: &ForgeRepo, _branch: &str) -> Result<ArchiveStream> {
+        Err(TeleStatsError::Unsupported("archive analysis is not supported by this service".to_string()))
+    }
+}
@@ -0,0 +1,285 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use serde::{Deserialize, Serialize};
+use std::ops::AddAssign;
+
+/// How the lines of some source split up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LineCounts {
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+impl AddAssign for LineCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blanks += other.blanks;
+    }
+}
+
+/// Comment and string syntax of a language.
+pub struct Syntax {
+    pub line: &'static [&'static str],
+    pub block: &'static [(&'static str, &'static str)],
+    /// Block comments can contain block comments (Rust, Haskell, ...).
+    pub nested: bool,
+    /// `(delimiter, may span lines)`, comment markers inside don't count.
+    pub strings: &'static [(&'static str, bool)],
+    /// `'x'` is a char literal, so a quote inside it opens no string.
+    pub chars: bool,
+}
+
+const NONE: Syntax = Syntax { line: &[], block: &[], nested: false, strings: &[], chars: false };
+
+const C_STRINGS: &[(&str, bool)] = &[("\"", false), ("'", false)];
+const JS_STRINGS: &[(&str, bool)] = &[("`", true), ("\"", false), ("'", false)];
+const PY_STRINGS: &[(&str, bool)] = &[("\"\"\"", true), ("'''", true), ("\"", false), ("'", false)];
+const DQ_STRINGS: &[(&str, bool)] = &[("\"", true)]; // no char literals, ' is a lifetime/quote/etc.
+
+const C: Syntax = Syntax { line: &["//"], block: &[("/*", "*/")], nested: false, strings: C_STRINGS, chars: false };
+const C_NESTED: Syntax = Syntax { line: &["//"], block: &[("/*", "*/")], nested: true, strings: DQ_STRINGS, chars: true };
+const JS: Syntax = Syntax { line: &["//"], block: &[("/*", "*/")], nested: false, strings: JS_STRINGS, chars: false };
+const HASH: Syntax = Syntax { line: &["#"], block: &[], nested: false, strings: C_STRINGS, chars: false };
+const PYTHON: Syntax = Syntax { line: &["#"], block: &[], nested: false, strings: PY_STRINGS, chars: false };
+const XML: Syntax = Syntax { line: &[], block: &[("<!--", "-->")], nested: false, strings: &[], chars: false };
+const LISP: Syntax = Syntax { line: &[";"], block: &[], nested: false, strings: &[("\"", true)], chars: false };
+const ML: Syntax = Syntax { line: &[], block: &[("(*", "*)")], nested: true, strings: DQ_STRINGS, chars: true };
+const DASH: Syntax = Syntax { line: &["--"], block: &[], nested: false, strings: DQ_STRINGS, chars: false };
+
+/// Syntax for a language name from the `languages` map, unknown ones count
+/// every non-blank line as code.
+pub fn syntax_for(language: &str) -> &'static Syntax {
+    match language {
+        "C" | "C++" | "H/H++" | "C#" | "Java" | "Go" | "Objective-C" | "Objective-C++" | "Groovy" | "Vala"
+        | "Pawn" | "GLSL" | "GDshaders" | "Haxe" | "GameMaker Language" | "Verilog" | "SystemVerilog"
+        | "D" => &C,
+        "Rust" | "Swift" | "Kotlin" | "Scala" | "V" => &C_NESTED,
+        "Dart" => &Syntax { line: &["//"], block: &[("/*", "*/")], nested: true, strings: PY_STRINGS, chars: false },
+        "JavaScript" | "TypeScript" | "React JSX" | "React TSX" | "Vue" => &JS,
+        "CSS" => &Syntax { line: &[], block: &[("/*", "*/")], nested: false, strings: C_STRINGS, chars: false },
+        "SCSS" | "Zig" => &Syntax { line: &["//"], block: &[("/*", "*/")], nested: false, strings: C_STRINGS, chars: false },
+        "PHP" => &Syntax { line: &["//", "#"], block: &[("/*", "*/")], nested: false, strings: C_STRINGS, chars: false },
+        "HTML" | "XML" => &XML,
+        "Python" => &PYTHON,
+        "GDscript" => &PYTHON,
+        "Shell" | "Bash" | "Perl" | "R" | "Tcl" | "AWK" | "Elixir" | "Crystal" | "Minecraft Function"
+        | "TOML" | "YAML" | "Makefile" | "Dockerfile" | "CMake" | "Meson" | "Just" => &HASH,
+        "Starlark" => &PYTHON,
+        "Ruby" => &Syntax { line: &["#"], block: &[("=begin", "=end")], nested: false, strings: C_STRINGS, chars: false },
+        "Nix" => &Syntax { line: &["#"], block: &[("/*", "*/")], nested: false, strings: &[("\"", true), ("''", true)], chars: false },
+        "Julia" => &Syntax { line: &["#"], block: &[("#=", "=#")], nested: true, strings: PY_STRINGS, chars: false },
+        "Nim" => &Syntax { line: &["#"], block: &[("#[", "]#")], nested: true, strings: PY_STRINGS, chars: false },
+        "PowerShell" => &Syntax { line: &["#"], block: &[("<#", "#>")], nested: false, strings: C_STRINGS, chars: false },
+        "CoffeeScript" => &Syntax { line: &["#"], block: &[("###", "###")], nested: false, strings: PY_STRINGS, chars: false },
+        "Lua" => &Syntax { line: &["--"], block: &[("--[[", "]]")], nested: false, strings: C_STRINGS, chars: false },
+        "Haskell" => &Syntax { line: &["--"], block: &[("{-", "-}")], nested: true, strings: DQ_STRINGS, chars: true },
+        "SQL" => &Syntax { line: &["--"], block: &[("/*", "*/")], nested: false, strings: &[("'", true), ("\"", false)], chars: false },
+        "Ada" | "Ada Body" | "Ada Spec" | "VHDL" => &DASH,
+        "Erlang" | "Erlang Header" => &Syntax { line: &["%"], block: &[], nested: false, strings: DQ_STRINGS, chars: false },
+        "Lisp" | "Emacs Lisp" | "Scheme" | "Racket" | "Clojure" | "ClojureScript" => &LISP,
+        "Assembly" | "AutoHotkey" => &Syntax { line: &[";"], block: &[], nested: false, strings: DQ_STRINGS, chars: false },
+        "Fortran" => &Syntax { line: &["!"], block: &[], nested: false, strings: C_STRINGS, chars: false },
+        "OCaml" | "OCaml Interface" | "Coq" => &ML,
+        "Prolog" => &Syntax { line: &["%"], block: &[("/*", "*/")], nested: false, strings: DQ_STRINGS, chars: false },
+        "MATLAB" => &Syntax { line: &["%"], block: &[("%{", "%}")], nested: false, strings: &[("\"", false)], chars: false },
+        "Mercury" => &Syntax { line: &["%"], block: &[], nested: false, strings: DQ_STRINGS, chars: false },
+        "F#" | "F# Interface" => &Syntax { line: &["//"], block: &[("(*", "*)")], nested: true, strings: DQ_STRINGS, chars: true },
+        "Pascal" => &Syntax { line: &["//"], block: &[("{", "}"), ("(*", "*)")], nested: false, strings: &[("'", false)], chars: false },
+        "VBScript" | "BASIC" | "Visual Basic" => &Syntax { line: &["'"], block: &[], nested: false, strings: &[("\"", false)], chars: false },
+        "Forth" => &Syntax { line: &["\\"], block: &[("( ", ")")], nested: false, strings: &[], chars: false },
+        "Rexx" => &Syntax { line: &["--"], block: &[("/*", "*/")], nested: true, strings: C_STRINGS, chars: false },
+        _ => &NONE,
+    }
+}
+
+/// Classifies every line of `content`. A line with any code outside of
+/// comments and strings' delimiters counts as code, one with only comment
+/// text as comment, one with only whitespace as blank.
+pub fn count_lines(content: &str, syntax: &Syntax) -> LineCounts {
+    let mut counts = LineCounts::default();
+    let mut block: Option<(&str, &str)> = None; // innermost open block comment
+    let mut depth = 0;
+    let mut string: Option<(&str, bool)> = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            counts.blanks += 1;
+            continue;
+        }
+
+        let mut has_code = string.is_some();
+        let mut has_comment = depth > 0;
+        let mut i = 0;
+
+        while i < line.len() {
+            let rest = &line[i..];
+
+            if let Some((open, close)) = block {
+                if syntax.nested && rest.starts_with(open) {
+                    depth += 1;
+                    i += open.len();
+                } else if rest.starts_with(close) {
+                    depth -= 1;
+                    if depth == 0 {
+                        block = None;
+                    }
+                    i += close.len();
+                } else {
+                    i += char_len(rest);
+                }
+                continue;
+            }
+
+            if let Some((delim, _)) = string {
+                if rest.starts_with('\\') {
+                    i += 1;
+                    i += line[i..].chars().next().map_or(0, char::len_utf8);
+                } else if rest.starts_with(delim) {
+                    string = None;
+                    i += delim.len();
+                } else {
+                    i += char_len(rest);
+                }
+                continue;
+            }
+
+            if rest.starts_with(char::is_whitespace) {
+                i += char_len(rest);
+            } else if syntax.line.iter().any(|marker| rest.starts_with(marker))
+                && !syntax.block.iter().any(|(open, _)| rest.starts_with(open))
+            {
+                has_comment = true;
+                break;
+            } else if let Some(&(open, close)) = syntax.block.iter().find(|(open, _)| rest.starts_with(open)) {
+                block = Some((open, close));
+                depth = 1;
+                has_comment = true;
+                i += open.len();
+            } else if let Some(len) = char_literal(line, i).filter(|_| syntax.chars) {
+                has_code = true;
+                i += len;
+            } else if let Some(&(delim, multiline)) = syntax.strings.iter().find(|(delim, _)| rest.starts_with(delim)) {
+                string = Some((delim, multiline));
+                has_code = true;
+                i += delim.len();
+            } else {
+                has_code = true;
+                i += char_len(rest);
+            }
+        }
+
+        // an unterminated one-line string (a lifetime, a stray quote) ends here
+        if matches!(string, Some((_, false))) {
+            string = None;
+        }
+
+        if has_code {
+            counts.code += 1;
+        } else if has_comment {
+            counts.comments += 1;
+        } else {
+            counts.blanks += 1;
+        }
+    }
+
+    counts
+}
+
+/// Length of the char literal at `line[i..]`, like `'"'` or `'\u{2014}'`.
+/// Lifetimes and primed names (`'a`, `x'`) are not ones.
+fn char_literal(line: &str, i: usize) -> Option<usize> {
+    let rest = line[i..].strip_prefix('\'')?;
+    if line[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let body = match rest.strip_prefix('\\') {
+        Some(escaped) => {
+            let first = char_len(escaped); // '\'' escapes the quote itself
+            1 + first + escaped.get(first..)?.find('\'').filter(|&end| end <= 8)?
+        }
+        None => char_len(rest),
+    };
+    rest.get(body..)?.starts_with('\'').then_some(body + 2)
+}
+
+fn char_len(rest: &str) -> usize {
+    rest.chars().next().map_or(1, char::len_utf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(code: usize, comments: usize, blanks: usize) -> LineCounts {
+        LineCounts { code, comments, blanks }
+    }
+
+    #[test]
+    fn nested_block_comments() {
+        let source = "/* outer\n/* inner */\nstill comment */\nfn main() {}\n";
+        assert_eq!(count_lines(source, syntax_for("Rust")), counts(1, 3, 0));
+        // C does not nest, the first */ closes the comment
+        let source = "/* /* */ int x;\n";
+        assert_eq!(count_lines(source, syntax_for("C")), counts(1, 0, 0));
+    }
+
+    #[test]
+    fn comment_markers_inside_strings() {
+        let source = "let a = \"/* not a comment\";\nlet b = \"// nor this\";\nlet c = 1;\n";
+        assert_eq!(count_lines(source, syntax_for("Rust")), counts(3, 0, 0));
+        let source = "s = '# kept'\n# comment\n";
+        assert_eq!(count_lines(source, syntax_for("Python")), counts(1, 1, 0));
+    }
+
+    #[test]
+    fn multiline_strings_are_code() {
+        let source = "let s = \"first\n// still the string\n\";\n";
+        assert_eq!(count_lines(source, syntax_for("Rust")), counts(3, 0, 0));
+    }
+
+    #[test]
+    fn lua_block_comments() {
+        let source = "--[[ block\nstill ]]\n-- line\nlocal x = 1\n";
+        assert_eq!(count_lines(source, syntax_for("Lua")), counts(1, 3, 0));
+    }
+
+    #[test]
+    fn julia_block_comments() {
+        let source = "#= outer\n#= inner =#\n=#\nx = 1 # trailing\n";
+        assert_eq!(count_lines(source, syntax_for("Julia")), counts(1, 3, 0));
+    }
+
+    #[test]
+    fn nim_block_comments() {
+        let source = "#[ outer\n#[ inner ]#\n]#\n\nlet x = 1\n";
+        assert_eq!(count_lines(source, syntax_for("Nim")), counts(1, 3, 1));
+    }
+
+    #[test]
+    fn rust_char_literals_open_no_string() {
+        let source = "let quote = '\"';\n// comment\nlet escaped = '\\'';\nfn f<'a>(x: &'a str) {}\n// comment\n";
+        assert_eq!(count_lines(source, syntax_for("Rust")), counts(3, 2, 0));
+    }
+
+    #[test]
+    fn quote_at_the_end_of_a_line() {
+        assert_eq!(count_lines("let c = '\\", syntax_for("Rust")), counts(1, 0, 0));
+        assert_eq!(count_lines("let c = '", syntax_for("Rust")), counts(1, 0, 0));
+    }
+
+    #[test]
+    fn dart_single_quoted_strings() {
+        let source = "var u = '/* x';\nvar y = 1;\nvar z = 2;\n";
+        assert_eq!(count_lines(source, syntax_for("Dart")), counts(3, 0, 0));
+        let source = "var s = '''\n// still the string\n''';\n";
+        assert_eq!(count_lines(source, syntax_for("Dart")), counts(3, 0, 0));
+    }
+
+    #[test]
+    fn unknown_languages_count_everything_as_code() {
+        assert_eq!(count_lines("a\n\n// b\n", syntax_for("Brainfuck")), counts(2, 0, 1));
+    }
+}
@@ -0,0 +1,310 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use std::collections::HashMap;
+use std::path::Path;
+
+// files known by their whole name, the `filenames` config block adds to these
+const FILENAMES: &[(&str, &str)] = &[
+    ("Dockerfile", "Dockerfile"),
+    ("Containerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+    ("makefile", "Makefile"),
+    ("GNUmakefile", "Makefile"),
+    ("CMakeLists.txt", "CMake"),
+    ("Rakefile", "Ruby"),
+    ("Gemfile", "Ruby"),
+    ("Vagrantfile", "Ruby"),
+    ("Podfile", "Ruby"),
+    ("Jenkinsfile", "Groovy"),
+    ("SConstruct", "Python"),
+    ("SConscript", "Python"),
+    ("Snakefile", "Python"),
+    ("BUILD", "Starlark"),
+    ("BUILD.bazel", "Starlark"),
+    ("WORKSPACE", "Starlark"),
+    ("meson.build", "Meson"),
+    ("justfile", "Just"),
+    ("PKGBUILD", "Shell"),
+    (".bashrc", "Shell"),
+    (".bash_profile", "Shell"),
+    (".zshrc", "Shell"),
+    (".profile", "Shell"),
+];
+
+// interpreter in a `#!` line -> language
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("python", "Python"),
+    ("pypy", "Python"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("dash", "Shell"),
+    ("ksh", "Shell"),
+    ("fish", "Shell"),
+    ("node", "JavaScript"),
+    ("nodejs", "JavaScript"),
+    ("deno", "TypeScript"),
+    ("bun", "TypeScript"),
+    ("ts-node", "TypeScript"),
+    ("ruby", "Ruby"),
+    ("perl", "Perl"),
+    ("php", "PHP"),
+    ("lua", "Lua"),
+    ("luajit", "Lua"),
+    ("Rscript", "R"),
+    ("julia", "Julia"),
+    ("tclsh", "Tcl"),
+    ("wish", "Tcl"),
+    ("awk", "AWK"),
+    ("gawk", "AWK"),
+    ("elixir", "Elixir"),
+    ("crystal", "Crystal"),
+    ("pwsh", "PowerShell"),
+    ("groovy", "Groovy"),
+    ("scala", "Scala"),
+    ("runghc", "Haskell"),
+    ("runhaskell", "Haskell"),
+    ("swift", "Swift"),
+    ("dart", "Dart"),
+    ("racket", "Racket"),
+    ("guile", "Scheme"),
+    ("sbcl", "Lisp"),
+    ("escript", "Erlang"),
+];
+
+// extensionless files that are never worth a request
+const NOT_CODE: &[&str] = &[
+    "LICENSE", "LICENCE", "COPYING", "README", "CHANGELOG", "CHANGES", "AUTHORS",
+    "CONTRIBUTORS", "NOTICE", "CODEOWNERS", "VERSION", "TODO", "HISTORY", "MAINTAINERS",
+];
+
+// extensions shared by several languages, settled by looking at the content
+const AMBIGUOUS: &[&str] = &["h", "m", "pl", "v"];
+
+/// What the path alone says about a file.
+#[derive(Debug, Clone)]
+pub enum Candidate {
+    Language(String),
+    /// Needs the content: a shebang or a heuristic decides, `fallback`
+    /// is the configured language for the extension, if any.
+    Content { fallback: Option<String> },
+    NotCode,
+}
+
+/// Built-in filename map with the config's on top, "" removes an entry.
+pub fn filename_map(config: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut filenames: HashMap<String, String> = FILENAMES
+        .iter()
+        .map(|(name, lang)| (name.to_string(), lang.to_string()))
+        .collect();
+    for (name, lang) in config {
+        if lang.is_empty() {
+            filenames.remove(name);
+        } else {
+            filenames.insert(name.clone(), lang.clone());
+        }
+    }
+    filenames
+}
+
+/// Exact filename, then extension. Extensionless files and ambiguous
+/// extensions are left to `by_content`.
+pub fn by_path(path: &str, filenames: &HashMap<String, String>, languages: &HashMap<String, String>) -> Candidate {
+    let file = Path::new(path);
+    let Some(name) = file.file_name().and_then(|n| n.to_str()) else {
+        return Candidate::NotCode;
+    };
+
+    if let Some(lang) = filenames.get(name) {
+        return Candidate::Language(lang.clone());
+    }
+
+    match file.extension().and_then(|e| e.to_str()) {
+        Some(ext) => match languages.get(ext) {
+            Some(lang) if AMBIGUOUS.contains(&ext) => Candidate::Content { fallback: Some(lang.clone()) },
+            Some(lang) => Candidate::Language(lang.clone()),
+            None => Candidate::NotCode,
+        },
+        None if name.starts_with('.') => Candidate::NotCode, // .gitignore, .editorconfig, ...
+        None if NOT_CODE.contains(&name) => Candidate::NotCode,
+        None => Candidate::Content { fallback: None },
+    }
+}
+
+/// Language of a file once its content is known, None = not code.
+pub fn resolve(path: &str, candidate: Candidate, content: &str) -> Option<String> {
+    match candidate {
+        Candidate::Language(lang) => Some(lang),
+        Candidate::Content { fallback } => by_content(path, content, fallback),
+        Candidate::NotCode => None,
+    }
+}
+
+/// Shebang first, then the heuristics for ambiguous extensions, then the fallback.
+pub fn by_content(path: &str, content: &str, fallback: Option<String>) -> Option<String> {
+    if let Some(lang) = shebang(content) {
+        return Some(lang.to_string());
+    }
+
+    let heuristic = match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("h") => header(content),
+        Some("m") => dot_m(content),
+        Some("pl") => dot_pl(content),
+        Some("v") => dot_v(content),
+        _ => None,
+    };
+
+    heuristic.map(str::to_string).or(fallback)
+}
+
+fn shebang(content: &str) -> Option<&'static str> {
+    let line = content.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        // `#!/usr/bin/env -S python3 -u`
+        interpreter = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+
+    // python3.11 -> python
+    let interpreter = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    INTERPRETERS
+        .iter()
+        .find(|(name, _)| *name == interpreter)
+        .map(|(_, lang)| *lang)
+}
+
+fn has_line_starting(content: &str, prefixes: &[&str]) -> bool {
+    content
+        .lines()
+        .map(str::trim_start)
+        .any(|line| prefixes.iter().any(|prefix| line.starts_with(prefix)))
+}
+
+fn header(content: &str) -> Option<&'static str> {
+    if has_line_starting(content, &["@interface", "@protocol", "@property", "@end", "#import"]) {
+        Some("Objective-C")
+    } else if has_line_starting(content, &["class ", "namespace ", "template", "public:", "private:", "protected:", "#include <iostream>", "#include <string>", "#include <vector>"])
+        || content.contains("std::")
+    {
+        Some("C++")
+    } else if has_line_starting(content, &["#include", "#define", "#ifndef", "typedef "]) {
+        Some("C")
+    } else {
+        None
+    }
+}
+
+fn dot_m(content: &str) -> Option<&'static str> {
+    if has_line_starting(content, &["@interface", "@implementation", "@protocol", "#import", "#include"]) {
+        Some("Objective-C")
+    } else if has_line_starting(content, &[":- module", ":- import_module"]) {
+        Some("Mercury")
+    } else if has_line_starting(content, &["function ", "%", "end"]) {
+        Some("MATLAB")
+    } else {
+        None
+    }
+}
+
+fn dot_pl(content: &str) -> Option<&'static str> {
+    if has_line_starting(content, &["use strict", "use warnings", "my ", "sub ", "package "]) {
+        Some("Perl")
+    } else if content.lines().any(|line| line.contains(":-")) {
+        Some("Prolog")
+    } else {
+        None
+    }
+}
+
+fn dot_v(content: &str) -> Option<&'static str> {
+    if has_line_starting(content, &["endmodule", "always @", "always_ff", "assign ", "input ", "output "]) {
+        Some("Verilog")
+    } else if has_line_starting(content, &["Theorem ", "Lemma ", "Proof.", "Qed.", "Require Import", "Inductive ", "Definition "]) {
+        Some("Coq")
+    } else if has_line_starting(content, &["fn ", "pub fn ", "module main", "import "]) {
+        Some("V")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(path: &str) -> Candidate {
+        let languages = HashMap::from([("js".to_string(), "JavaScript".to_string()), ("yml".to_string(), "YAML".to_string())]);
+        by_path(path, &filename_map(&HashMap::new()), &languages)
+    }
+
+    #[test]
+    fn dotfiles_with_an_extension_are_code() {
+        assert!(matches!(detect(".eslintrc.js"), Candidate::Language(lang) if lang == "JavaScript"));
+        assert!(matches!(detect("ci/.gitlab-ci.yml"), Candidate::Language(lang) if lang == "YAML"));
+    }
+
+    #[test]
+    fn bare_dotfiles_are_not_code() {
+        assert!(matches!(detect(".gitignore"), Candidate::NotCode));
+        assert!(matches!(detect("src/.editorconfig"), Candidate::NotCode));
+    }
+
+    #[test]
+    fn only_listed_extensions_count() {
+        assert!(matches!(detect("src/main.rs"), Candidate::NotCode));
+        assert!(matches!(detect("web/app.js"), Candidate::Language(lang) if lang == "JavaScript"));
+    }
+
+    #[test]
+    fn known_dotfiles_keep_their_language() {
+        assert!(matches!(detect(".bashrc"), Candidate::Language(lang) if lang == "Shell"));
+    }
+
+    #[test]
+    fn files_known_by_their_name() {
+        assert!(matches!(detect("Dockerfile"), Candidate::Language(lang) if lang == "Dockerfile"));
+        assert!(matches!(detect("docker/Containerfile"), Candidate::Language(lang) if lang == "Dockerfile"));
+        assert!(matches!(detect("src/Makefile"), Candidate::Language(lang) if lang == "Makefile"));
+        assert!(matches!(detect("CMakeLists.txt"), Candidate::Language(lang) if lang == "CMake"));
+        assert!(matches!(detect("LICENSE"), Candidate::NotCode));
+        assert!(matches!(detect("bin/deploy"), Candidate::Content { fallback: None }));
+    }
+
+    #[test]
+    fn shebang_names_the_interpreter() {
+        assert_eq!(shebang("#!/usr/bin/env -S python3.11 -u\nprint()"), Some("Python"));
+        assert_eq!(shebang("#!/bin/sh\nset -e"), Some("Shell"));
+        assert_eq!(shebang("#!/usr/bin/env node"), Some("JavaScript"));
+        assert_eq!(shebang("#!/usr/bin/env FOO=1 ruby"), Some("Ruby"));
+        assert_eq!(shebang("#!/usr/bin/unknown"), None);
+        assert_eq!(shebang("echo hi\n#!/bin/sh"), None);
+    }
+
+    #[test]
+    fn headers_by_content() {
+        let by = |content| by_content("include/x.h", content, Some("H/H++".to_string()));
+        assert_eq!(by("#import <Foundation/Foundation.h>\n@interface Foo\n").as_deref(), Some("Objective-C"));
+        assert_eq!(by("namespace app {\nclass Foo;\n}\n").as_deref(), Some("C++"));
+        assert_eq!(by("#ifndef X_H\n#define X_H\nint x(void);\n").as_deref(), Some("C"));
+        assert_eq!(by("").as_deref(), Some("H/H++"));
+    }
+
+    #[test]
+    fn dot_m_and_dot_pl_by_content() {
+        assert_eq!(by_content("a.m", "@implementation Foo\n@end\n", None).as_deref(), Some("Objective-C"));
+        assert_eq!(by_content("a.m", "function y = f(x)\n  y = x;\nend\n", None).as_deref(), Some("MATLAB"));
+        assert_eq!(by_content("a.m", ":- module a.\n", None).as_deref(), Some("Mercury"));
+        assert_eq!(by_content("a.pl", "use strict;\nmy $x = 1;\n", None).as_deref(), Some("Perl"));
+        assert_eq!(by_content("a.pl", "parent(tom, bob).\nfather(X) :- parent(X, _).\n", None).as_deref(), Some("Prolog"));
+    }
+
+    #[test]
+    fn dot_v_by_content() {
+        assert_eq!(by_content("a.v", "module top(input clk);\nendmodule\n", None).as_deref(), Some("Verilog"));
+        assert_eq!(by_content("a.v", "Theorem t : True.\nProof. trivial. Qed.\n", None).as_deref(), Some("Coq"));
+        assert_eq!(by_content("a.v", "module main\n\nfn main() {}\n", None).as_deref(), Some("V"));
+        assert_eq!(by_content("a.v", "#!/usr/bin/env python3\n", None).as_deref(), Some("Python"));
+    }
+}
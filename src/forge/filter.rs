@@ -0,0 +1,285 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::{Result, TeleStatsError};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+
+// dependencies and bundled copies, same idea as linguist's vendor.yml
+const VENDORED: &[&str] = &[
+    "node_modules/", "bower_components/", "jspm_packages/", "vendor/", "vendors/",
+    "third_party/", "thirdparty/", "3rdparty/", "Godeps/", "Pods/", "Carthage/",
+    ".yarn/", "dist/", "site-packages/", "*.min.js", "*.min.css", "*-min.js",
+];
+
+// machine written code, same idea as linguist's generated.rb
+const GENERATED: &[&str] = &[
+    "*.pb.go", "*.pb.cc", "*.pb.h", "*.pb.swift", "*_pb2.py", "*_pb2_grpc.py", "*_pb.js",
+    "*.g.dart", "*.freezed.dart", "*.generated.*", "*.designer.cs", "*.Designer.cs",
+    "*_generated.go", "*.lock", "package-lock.json", "pnpm-lock.yaml", "go.sum",
+];
+
+/// Per-service `paths` block of the config.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PathRules {
+    /// Only count files matching one of these, empty = everything.
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default = "default_true")]
+    pub exclude_vendored: bool,
+    #[serde(default = "default_true")]
+    pub exclude_generated: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for PathRules {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            exclude_vendored: true,
+            exclude_generated: true,
+        }
+    }
+}
+
+/// Compiled `PathRules`, shared by every repo of a run.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    rules: PathRules,
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    vendored: GlobSet,
+    generated: GlobSet,
+}
+
+impl PathFilter {
+    pub fn new(rules: &PathRules) -> Result<Self> {
+        let include = if rules.include.is_empty() {
+            None
+        } else {
+            Some(glob_set(&rules.include)?)
+        };
+
+        Ok(Self {
+            rules: rules.clone(),
+            include,
+            exclude: glob_set(&rules.exclude)?,
+            vendored: glob_set(VENDORED)?,
+            generated: glob_set(GENERATED)?,
+        })
+    }
+
+    /// Changes whenever the rules do, cached counts made with other rules are stale.
+    pub fn fingerprint(&self) -> String {
+        format!("{:?}", self.rules)
+    }
+
+    /// The filter for one repo, `gitattributes` is its root `.gitattributes`.
+    pub fn for_repo(&self, gitattributes: Option<&str>) -> RepoFilter<'_> {
+        RepoFilter {
+            filter: self,
+            attributes: gitattributes.map(parse_attributes).unwrap_or_default(),
+        }
+    }
+}
+
+/// `linguist-*` overrides from a `.gitattributes` line, None = not mentioned.
+struct Attributes {
+    matcher: GlobMatcher,
+    vendored: Option<bool>,
+    generated: Option<bool>,
+}
+
+pub struct RepoFilter<'a> {
+    filter: &'a PathFilter,
+    attributes: Vec<Attributes>,
+}
+
+impl RepoFilter<'_> {
+    pub fn counts(&self, path: &str) -> bool {
+        let filter = self.filter;
+        if let Some(include) = &filter.include {
+            if !include.is_match(path) {
+                return false;
+            }
+        }
+        if filter.exclude.is_match(path) {
+            return false;
+        }
+
+        // like git, the last line mentioning an attribute wins
+        let attribute = |pick: fn(&Attributes) -> Option<bool>| {
+            self.attributes
+                .iter()
+                .rev()
+                .filter(|attrs| attrs.matcher.is_match(path))
+                .find_map(pick)
+        };
+
+        let vendored = attribute(|a| a.vendored).unwrap_or_else(|| filter.vendored.is_match(path));
+        let generated = attribute(|a| a.generated).unwrap_or_else(|| filter.generated.is_match(path));
+
+        !(vendored && filter.rules.exclude_vendored || generated && filter.rules.exclude_generated)
+    }
+}
+
+fn parse_attributes(content: &str) -> Vec<Attributes> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?;
+            let mut attrs = (None, None);
+            for attr in parts {
+                let (name, value) = match attr.split_once('=') {
+                    Some((name, value)) => (name, value != "false"),
+                    None => match attr.strip_prefix(['-', '!']) {
+                        Some(name) => (name, false),
+                        None => (attr, true),
+                    },
+                };
+                match name {
+                    "linguist-vendored" => attrs.0 = Some(value),
+                    "linguist-generated" => attrs.1 = Some(value),
+                    _ => {}
+                }
+            }
+
+            if attrs == (None, None) {
+                return None;
+            }
+
+            match glob(pattern).map(|g| g.compile_matcher()) {
+                Ok(matcher) => Some(Attributes {
+                    matcher,
+                    vendored: attrs.0,
+                    generated: attrs.1,
+                }),
+                Err(e) => {
+                    log::warn!("ignoring .gitattributes pattern {}: {}", pattern, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn glob_set<S: AsRef<str>>(patterns: &[S]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(glob(pattern.as_ref())?);
+    }
+    builder.build().map_err(|e| TeleStatsError::Config(format!("invalid path pattern: {}", e)))
+}
+
+/// gitignore-style pattern to a glob: no slash = any depth, leading slash
+/// = repo root, trailing slash = everything below that directory.
+fn glob(pattern: &str) -> Result<Glob> {
+    let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+    let mut glob = pattern.trim_start_matches('/').to_string();
+    if !anchored && !glob.starts_with("**/") {
+        glob.insert_str(0, "**/");
+    }
+    if glob.ends_with('/') {
+        glob.push_str("**");
+    }
+
+    GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| TeleStatsError::Config(format!("invalid path pattern {}: {}", pattern, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> PathFilter {
+        let rules = PathRules {
+            include: include.iter().map(|s| s.to_string()).collect(),
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            ..PathRules::default()
+        };
+        PathFilter::new(&rules).unwrap()
+    }
+
+    #[test]
+    fn include_and_exclude_globs() {
+        let filter = filter(&["src/", "*.rs"], &["src/legacy/", "*_test.rs"]);
+        let repo = filter.for_repo(None);
+        assert!(repo.counts("src/main.c"));
+        assert!(repo.counts("build.rs"));
+        assert!(repo.counts("tools/gen/mod.rs"));
+        assert!(!repo.counts("docs/index.md"));
+        assert!(!repo.counts("src/legacy/old.c"));
+        assert!(!repo.counts("tests/parser_test.rs"));
+    }
+
+    #[test]
+    fn vendored_and_generated_are_left_out_by_default() {
+        let filter = filter(&[], &[]);
+        let repo = filter.for_repo(None);
+        assert!(repo.counts("src/app.js"));
+        assert!(!repo.counts("node_modules/left-pad/index.js"));
+        assert!(!repo.counts("web/vendor/jquery.js"));
+        assert!(!repo.counts("static/app.min.js"));
+        assert!(!repo.counts("api/service.pb.go"));
+        assert!(!repo.counts("Cargo.lock"));
+    }
+
+    #[test]
+    fn vendored_counts_when_not_excluded() {
+        let rules = PathRules { exclude_vendored: false, ..PathRules::default() };
+        let filter = PathFilter::new(&rules).unwrap();
+        let repo = filter.for_repo(None);
+        assert!(repo.counts("vendor/lib.go"));
+        assert!(!repo.counts("api/service.pb.go"));
+    }
+
+    #[test]
+    fn gitattributes_override_the_defaults() {
+        let filter = filter(&[], &[]);
+        let repo = filter.for_repo(Some(
+            "third_party/ours/** -linguist-vendored\n\
+             lib/bundle.js linguist-vendored\n\
+             schema/*.go linguist-generated=true\n\
+             *.pb.go linguist-generated=false\n",
+        ));
+        assert!(repo.counts("third_party/ours/main.c"));
+        assert!(!repo.counts("third_party/theirs/main.c"));
+        assert!(!repo.counts("lib/bundle.js"));
+        assert!(!repo.counts("schema/types.go"));
+        assert!(repo.counts("api/service.pb.go"));
+    }
+
+    #[test]
+    fn the_last_matching_line_wins() {
+        let filter = filter(&[], &[]);
+        let repo = filter.for_repo(Some("gen/** linguist-generated\ngen/keep.rs -linguist-generated\n"));
+        assert!(!repo.counts("gen/out.rs"));
+        assert!(repo.counts("gen/keep.rs"));
+    }
+
+    #[test]
+    fn parse_attributes_skips_comments_and_unrelated_lines() {
+        let attributes = parse_attributes(
+            "# linguist-vendored in a comment\n\
+             \n\
+             *.txt text eol=lf\n\
+             docs/** -linguist-vendored linguist-generated\n\
+             build/** linguist-vendored=false -linguist-generated\n",
+        );
+        assert_eq!(attributes.len(), 2);
+        assert_eq!((attributes[0].vendored, attributes[0].generated), (Some(false), Some(true)));
+        assert_eq!((attributes[1].vendored, attributes[1].generated), (Some(false), Some(false)));
+        assert!(attributes[0].matcher.is_match("docs/api/index.md"));
+    }
+}
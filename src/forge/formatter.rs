@@ -0,0 +1,428 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{ForgeSettings, ForgeStats, RepoStats, TopReposSettings};
+use crate::card::StatsCard;
+use crate::error::Result;
+use crate::services::RenderContext;
+use crate::template::{self, MessageFormat};
+use chrono::Local;
+use serde::Serialize;
+
+pub fn escape_markdown(text: &str) -> String {
+    text.replace("_", "\\_")
+        .replace("*", "\\*")
+        .replace("[", "\\[")
+        .replace("]", "\\]")
+        .replace("(", "\\(")
+        .replace(")", "\\)")
+        .replace("~", "\\~")
+        .replace("`", "\\`")
+        .replace(">", "\\>")
+        .replace("#", "\\#")
+        .replace("+", "\\+")
+        .replace("-", "\\-")
+        .replace("=", "\\=")
+        .replace("|", "\\|")
+        .replace("{", "\\{")
+        .replace("}", "\\}")
+        .replace(".", "\\.")
+        .replace("!", "\\!")
+}
+
+// inside (...) of a link only ')' and '\' have to be escaped
+pub(crate) fn escape_link_url(url: &str) -> String {
+    url.replace("\\", "\\\\").replace(")", "\\)")
+}
+
+pub fn share_arrow(current: f64, previous: Option<f64>) -> &'static str {
+    let diff = current - previous.unwrap_or(0.0);
+    if diff >= 0.01 {
+        "▲"
+    } else if diff <= -0.01 {
+        "▼"
+    } else {
+        ""
+    }
+}
+
+pub fn format_activity(last_activity: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(last_activity)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| last_activity.to_string())
+}
+
+fn format_date(last_activity: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(last_activity)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| last_activity.to_string())
+}
+
+/// Display name of a forge service.
+pub fn forge_label(service: &str) -> &str {
+    match service {
+        "github" => "GitHub",
+        "gitlab" => "GitLab",
+        "gitea" => "Gitea",
+        "bitbucket" => "Bitbucket",
+        "local" => "Local",
+        other => other,
+    }
+}
+
+/// Optional parts of the message, straight from the service config.
+pub struct MessageOptions<'a> {
+    pub quote: bool,
+    pub top_repos: &'a TopReposSettings,
+}
+
+/// How the header line links back to the user on a given forge.
+pub struct ProfileLink<'a> {
+    pub forge_name: &'a str,
+    pub url: String,
+}
+
+/// A forge service's post: the config's template if it has one, the
+/// built-in layout otherwise.
+pub fn render_message(stats: &ForgeStats, settings: &ForgeSettings, username: &str, link: &ProfileLink, context: &RenderContext) -> Result<String> {
+    let options = MessageOptions {
+        quote: settings.quote,
+        top_repos: &settings.top_repos,
+    };
+
+    match settings.template_path() {
+        Some(path) => render_template(path, settings.parse_mode, stats, username, link, &options, context),
+        None => Ok(format_stats(stats, username, link, &options, context)),
+    }
+}
+
+/// Same numbers as the message, laid out for the svg card.
+pub fn stats_card(stats: &ForgeStats, username: &str, link: &ProfileLink) -> StatsCard {
+    StatsCard {
+        title: format!("{}'s {} Stats", username, link.forge_name),
+        totals: vec![
+            ("Total code lines".to_string(), stats.total_lines.to_string()),
+            ("Repositories".to_string(), format!("{} ({} public)", stats.total_repos, stats.public_repos)),
+            ("Languages".to_string(), stats.total_languages.to_string()),
+            ("Favorite language".to_string(), stats.favorite_language.clone()),
+        ],
+        languages: stats.language_shares(),
+        last_activity: format_activity(&stats.last_activity),
+    }
+}
+
+pub fn format_stats(stats: &ForgeStats, username: &str, link: &ProfileLink, options: &MessageOptions, context: &RenderContext) -> String {
+    let now = Local::now();
+    let date = now.format("%Y\\-%m\\-%d").to_string();
+    let time = now.format("%H:%M:%S").to_string();
+
+    let username = escape_markdown(username);
+    let mut total_lines = stats.total_lines.to_string();
+    if let Some(baseline) = &context.baseline {
+        let delta = stats.total_lines as i64 - baseline.total_lines as i64;
+        total_lines.push_str(&format!(" ({:+} {})", delta, context.period));
+    }
+    let total_lines = escape_markdown(&total_lines);
+    let total_langs = escape_markdown(&stats.total_languages.to_string());
+    let fav_lang = escape_markdown(&stats.favorite_language);
+    let total_repos = escape_markdown(&stats.total_repos.to_string());
+    let public_repos = escape_markdown(&stats.public_repos.to_string());
+
+    let activity = escape_markdown(&format_activity(&stats.last_activity));
+    let forge = match link.url.as_str() {
+        "" => escape_markdown(link.forge_name), // nothing to link to, e.g. local repos
+        url => format!("[{}]({})", escape_markdown(link.forge_name), escape_link_url(url)),
+    };
+
+    let mut message = format!(
+        "User statistics for {} on {}\n\
+        *Total code lines:* {}\n\
+        *Comment lines:* {}\n\
+        *Blank lines:* {}\n\
+        *Last updated:* {} \\| {}\n\
+        *Total languages:* {}\n\
+        *Favorite language:* {}\n\
+        *Repositories:* {}\n\
+        *Public repositories:* {}\n\
+        *Last activity:* {}\n\n\
+        *Languages*",
+        username,
+        forge,
+        total_lines,
+        stats.line_counts.comments,
+        stats.line_counts.blanks,
+        date,
+        time,
+        total_langs,
+        fav_lang,
+        total_repos,
+        public_repos,
+        activity
+    );
+
+    message.push_str(&format_languages(stats, options.quote, context));
+    message.push_str(&format_top_repos(stats, options));
+    message.push_str(&format_incomplete(stats));
+    message
+}
+
+/// Bulleted language list, MarkdownV2.
+pub fn format_languages(stats: &ForgeStats, quote: bool, context: &RenderContext) -> String {
+    let mut message = String::new();
+    let bullet = if quote { ">•" } else { "•" };
+    for lang_stat in &stats.language_stats {
+        let lang_name = escape_markdown(&lang_stat.name);
+        let percentage = escape_markdown(&format!("{:.2}", lang_stat.percentage));
+        let trend = match &context.baseline {
+            Some(baseline) => share_arrow(lang_stat.percentage, baseline.language_share(&lang_stat.name)),
+            None => "",
+        };
+        let trend = if trend.is_empty() { String::new() } else { format!(" {}", trend) };
+        message.push_str(&format!("\n{} {}: {}%{}", bullet, lang_name, percentage, trend));
+    }
+
+    message
+}
+
+/// "Top repositories" section, empty when turned off.
+pub fn format_top_repos(stats: &ForgeStats, options: &MessageOptions) -> String {
+    let repos = stats.top_repos(options.top_repos);
+    if repos.is_empty() {
+        return String::new();
+    }
+
+    let bullet = if options.quote { ">•" } else { "•" };
+    let mut message = "\n\n*Top repositories*".to_string();
+    for repo in repos {
+        message.push_str(&format!(
+            "\n{} {}: {} lines, {}, {}",
+            bullet,
+            escape_markdown(&repo.name),
+            repo.total_lines,
+            escape_markdown(repo.dominant_language().unwrap_or("None")),
+            escape_markdown(&format_date(&repo.last_activity))
+        ));
+    }
+
+    message
+}
+
+pub fn format_incomplete(stats: &ForgeStats) -> String {
+    if !stats.is_incomplete() {
+        return String::new();
+    }
+
+    format!(
+        "\n\n_Incomplete data: {} files and {} repositories could not be read_",
+        stats.skipped_files, stats.skipped_repos
+    )
+}
+
+/// Languages listed by `/repo`, the rest is summed up in one line.
+const REPO_LANGS: usize = 15;
+
+/// Reply to `/repo`: one repository's languages in lines and share.
+pub fn format_repo(forge: &str, repo: &RepoStats) -> String {
+    let mut message = format!(
+        "*{}* on {}\n\
+        *Code lines:* {}\n\
+        *Last activity:* {}\n\n\
+        *Languages*",
+        escape_markdown(&repo.name),
+        escape_markdown(forge_label(forge)),
+        repo.total_lines,
+        escape_markdown(&format_activity(&repo.last_activity))
+    );
+
+    let mut languages: Vec<_> = repo.languages.iter().collect();
+    languages.sort_by(|a, b| b.1.code.cmp(&a.1.code).then_with(|| a.0.cmp(b.0)));
+    let hidden = languages.len().saturating_sub(REPO_LANGS);
+    for (name, counts) in languages.into_iter().take(REPO_LANGS) {
+        let share = if repo.total_lines > 0 {
+            counts.code as f64 / repo.total_lines as f64 * 100.0
+        } else {
+            0.0
+        };
+        message.push_str(&format!(
+            "\n• {}: {} code, {} comments, {} blank \\({}%\\)",
+            escape_markdown(name),
+            counts.code,
+            counts.comments,
+            counts.blanks,
+            escape_markdown(&format!("{:.2}", share))
+        ));
+    }
+    if hidden > 0 {
+        message.push_str(&format!("\n• and {} more", hidden));
+    }
+
+    message
+}
+
+#[derive(Serialize)]
+struct TemplateLanguage {
+    name: String,
+    percentage: String, // already rounded, "71.17"
+    code: usize,
+    comments: usize,
+    blanks: usize,
+    trend: &'static str, // "▲", "▼" or "" without history
+}
+
+#[derive(Serialize)]
+struct TemplateRepo<'a> {
+    name: &'a str,
+    lines: usize,
+    language: &'a str, // dominant one
+    last_activity: String, // date only
+}
+
+#[derive(Serialize)]
+struct TemplateDelta {
+    total_lines: String, // signed, "+120" or "-4", so the escaper sees the sign
+    period: String,
+}
+
+/// Everything a message template can use.
+#[derive(Serialize)]
+struct TemplateContext<'a> {
+    forge: &'a str,
+    username: &'a str,
+    profile_url: &'a str,
+    quote: bool,
+    updated_date: String,
+    updated_time: String,
+    last_activity: String,
+    stats: &'a ForgeStats,
+    languages: Vec<TemplateLanguage>,
+    top_repos: Vec<TemplateRepo<'a>>,
+    delta: Option<TemplateDelta>,
+}
+
+pub fn render_template(
+    path: &str,
+    format: MessageFormat,
+    stats: &ForgeStats,
+    username: &str,
+    link: &ProfileLink,
+    options: &MessageOptions,
+    context: &RenderContext,
+) -> Result<String> {
+    let now = Local::now();
+    let counts = |name: &str| stats.language_counts.get(name).copied().unwrap_or_default();
+    let languages = stats.language_stats
+        .iter()
+        .map(|lang_stat| TemplateLanguage {
+            name: lang_stat.name.clone(),
+            percentage: format!("{:.2}", lang_stat.percentage),
+            code: counts(&lang_stat.name).code,
+            comments: counts(&lang_stat.name).comments,
+            blanks: counts(&lang_stat.name).blanks,
+            trend: context.baseline.as_ref()
+                .map(|baseline| share_arrow(lang_stat.percentage, baseline.language_share(&lang_stat.name)))
+                .unwrap_or(""),
+        })
+        .collect();
+    let top_repos = stats.top_repos(options.top_repos)
+        .into_iter()
+        .map(|repo| TemplateRepo {
+            name: &repo.name,
+            lines: repo.total_lines,
+            language: repo.dominant_language().unwrap_or("None"),
+            last_activity: format_date(&repo.last_activity),
+        })
+        .collect();
+    let delta = context.baseline.as_ref().map(|baseline| TemplateDelta {
+        total_lines: format!("{:+}", stats.total_lines as i64 - baseline.total_lines as i64),
+        period: context.period.clone(),
+    });
+
+    let template_context = TemplateContext {
+        forge: link.forge_name,
+        username,
+        profile_url: &link.url,
+        quote: options.quote,
+        updated_date: now.format("%Y-%m-%d").to_string(),
+        updated_time: now.format("%H:%M:%S").to_string(),
+        last_activity: format_activity(&stats.last_activity),
+        stats,
+        languages,
+        top_repos,
+        delta,
+    };
+
+    template::render(path, format, &template_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::counter::LineCounts;
+    use crate::history::Snapshot;
+    use std::collections::HashMap;
+
+    fn stats() -> ForgeStats {
+        ForgeStats {
+            total_lines: 96,
+            language_stats: Vec::new(),
+            language_lines: HashMap::new(),
+            line_counts: Default::default(),
+            language_counts: HashMap::new(),
+            total_languages: 3,
+            favorite_language: "Rust".to_string(),
+            total_repos: 2,
+            public_repos: 1,
+            last_activity: String::new(),
+            skipped_files: 1,
+            skipped_repos: 0,
+            repos: Vec::new(),
+        }
+    }
+
+    fn render(path: &str, format: MessageFormat) -> String {
+        let link = ProfileLink { forge_name: "GitHub", url: "https://github.com/user".to_string() };
+        let top_repos = TopReposSettings::default();
+        let options = MessageOptions { quote: false, top_repos: &top_repos };
+        let context = RenderContext {
+            baseline: Some(Snapshot { total_lines: 100, ..Default::default() }),
+            period: "this week".to_string(),
+            series: Vec::new(),
+        };
+        render_template(path, format, &stats(), "user", &link, &options, &context).unwrap()
+    }
+
+    #[test]
+    fn repo_reply_caps_the_language_list() {
+        let languages: HashMap<String, LineCounts> = (0..40)
+            .map(|i| (format!("Lang{}", i), LineCounts { code: 10, ..Default::default() }))
+            .collect();
+        let repo = RepoStats {
+            name: "monorepo".to_string(),
+            private: false,
+            last_activity: String::new(),
+            url: None,
+            remote: None,
+            total_lines: 400,
+            languages,
+        };
+        let message = format_repo("github", &repo);
+        assert_eq!(message.matches("\n• ").count(), REPO_LANGS + 1);
+        assert!(message.ends_with("\n• and 25 more"), "{}", message);
+    }
+
+    #[test]
+    fn markdown_template_escapes_negative_delta() {
+        let message = render("src/configs/templates/forge.md.tera", MessageFormat::MarkdownV2);
+        assert!(message.contains("*Total code lines:* 96 \\(\\-4 this week\\)"), "{}", message);
+        assert!(message.contains("*Total languages:* 3"));
+        assert!(message.contains("_Incomplete data: 1 files"));
+    }
+
+    #[test]
+    fn html_template_matches_markdown_lines() {
+        let message = render("src/configs/templates/forge.html.tera", MessageFormat::Html);
+        assert!(message.contains("<b>Total code lines:</b> 96 (-4 this week)"), "{}", message);
+        assert!(message.contains("<b>Total languages:</b> 3"));
+        assert!(message.contains("<i>Incomplete data: 1 files"));
+    }
+}
@@ -0,0 +1,172 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::{Result, TeleStatsError};
+use reqwest::header::HeaderMap;
+use reqwest::{Certificate, Client, ClientBuilder, Response, StatusCode};
+use std::fs;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::{sleep, sleep_until, Duration, Instant};
+
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(3600); // primary limits reset hourly
+
+/// GET wrapper shared by the forge clients: waits out rate limits and
+/// retries transient failures instead of handing them to the analyzer.
+pub struct ForgeHttp {
+    client: Client,
+    max_retries: u32,
+    resume_at: Mutex<Option<Instant>>, // set once any response says the quota is gone
+}
+
+impl ForgeHttp {
+    pub fn new(client: Client, max_retries: u32) -> Self {
+        Self {
+            client,
+            max_retries,
+            resume_at: Mutex::new(None),
+        }
+    }
+
+    /// Final response, successful or not; statuses are left to the caller.
+    /// Logs and errors never carry the query, some APIs take keys there.
+    pub async fn get(&self, url: &str) -> Result<Response> {
+        let mut attempt = 0;
+        let shown = without_query(url);
+
+        loop {
+            self.wait_for_quota().await;
+
+            match self.client.get(url).send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    let limit_wait = rate_limit_wait(response.headers());
+                    if let Some(wait) = limit_wait {
+                        self.pause_for(wait);
+                    }
+
+                    let throttled = status == StatusCode::TOO_MANY_REQUESTS
+                        || (status == StatusCode::FORBIDDEN && limit_wait.is_some());
+
+                    if throttled && attempt >= self.max_retries {
+                        return Err(TeleStatsError::RateLimited(format!("{} after {} retries: {}", status, attempt, shown)));
+                    } else if attempt >= self.max_retries {
+                        return Ok(response);
+                    } else if throttled {
+                        let wait = limit_wait.unwrap_or_else(|| backoff(attempt));
+                        self.pause_for(wait);
+                        log::warn!("rate limited ({}), waiting {}s: {}", status, wait.as_secs(), shown);
+                    } else if status.is_server_error() {
+                        let wait = backoff(attempt);
+                        log::warn!("{} from forge, retrying in {}s: {}", status, wait.as_secs(), shown);
+                        sleep(wait).await;
+                    } else {
+                        return Ok(response);
+                    }
+                }
+                Err(e) if attempt < self.max_retries && (e.is_timeout() || e.is_connect() || e.is_request()) => {
+                    let wait = backoff(attempt);
+                    log::warn!("request failed ({}), retrying in {}s: {}", e.without_url(), wait.as_secs(), shown);
+                    sleep(wait).await;
+                }
+                Err(e) => return Err(e.without_url().into()),
+            }
+
+            attempt += 1;
+        }
+    }
+
+    fn pause_for(&self, wait: Duration) {
+        let until = Instant::now() + wait;
+        let mut resume_at = self.resume_at.lock().unwrap();
+        if resume_at.is_none_or(|current| current < until) {
+            *resume_at = Some(until);
+        }
+    }
+
+    async fn wait_for_quota(&self) {
+        let resume_at = *self.resume_at.lock().unwrap();
+        if let Some(until) = resume_at {
+            if until > Instant::now() {
+                sleep_until(until).await;
+            }
+        }
+    }
+}
+
+/// TLS options of self-hosted forges: trust the PEM bundle at `ca_cert`
+/// ("" = system roots only) and, if asked, skip verification for `host`.
+pub fn with_tls(mut builder: ClientBuilder, ca_cert: Option<&str>, insecure_tls: bool, host: &str) -> Result<ClientBuilder> {
+    if let Some(ca_path) = ca_cert.filter(|p| !p.is_empty()) {
+        let pem = fs::read(ca_path)
+            .map_err(|e| TeleStatsError::Config(format!("failed to read ca bundle {}: {}", ca_path, e)))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| TeleStatsError::Config(format!("invalid ca bundle {}: {}", ca_path, e)))?;
+        for cert in certs {
+            builder = builder.add_root_certificate(cert); // trust company CA
+        }
+    }
+
+    if insecure_tls {
+        log::warn!("tls verification is disabled for {}", host);
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder)
+}
+
+fn without_query(url: &str) -> &str {
+    url.split_once('?').map_or(url, |(path, _)| path)
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(6)).min(MAX_BACKOFF)
+}
+
+fn header_u64(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+    names.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+    })
+}
+
+/// How long the forge asks us to back off, if at all. Covers GitHub
+/// (`X-RateLimit-*`, secondary limits via `Retry-After`) and GitLab
+/// (`RateLimit-*`, `Retry-After` on 429), Gitea/Forgejo only send the
+/// latter when a reverse proxy throttles them.
+fn rate_limit_wait(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(secs) = header_u64(headers, &["retry-after"]) {
+        return Some(Duration::from_secs(secs).min(MAX_RATE_LIMIT_WAIT));
+    }
+
+    let remaining = header_u64(headers, &["x-ratelimit-remaining", "ratelimit-remaining"])?;
+    if remaining > 0 {
+        return None;
+    }
+
+    let reset = header_u64(headers, &["x-ratelimit-reset", "ratelimit-reset"])?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let wait = Duration::from_secs(reset.saturating_sub(now) + 1);
+    Some(wait.min(MAX_RATE_LIMIT_WAIT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_is_dropped() {
+        assert_eq!(without_query("https://api.example.com/v1/?key=secret&x=1"), "https://api.example.com/v1/");
+        assert_eq!(without_query("https://api.example.com/players/1"), "https://api.example.com/players/1");
+    }
+
+    #[tokio::test]
+    async fn errors_do_not_carry_the_url() {
+        let http = ForgeHttp::new(Client::new(), 0);
+        let error = http.get("http://127.0.0.1:1/resolve?key=secret").await.unwrap_err();
+        assert!(!error.to_string().contains("secret"), "{}", error);
+    }
+}
@@ -0,0 +1,15 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+pub mod http;
+pub mod analyzer;
+pub mod archive;
+pub mod cache;
+pub mod counter;
+pub mod detect;
+pub mod filter;
+pub mod store;
+pub mod service;
+pub mod formatter;
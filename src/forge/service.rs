@@ -0,0 +1,160 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::analyzer::ForgeAnalyzer;
+use super::client::ForgeClient;
+use super::formatter::{self, ProfileLink};
+use super::store;
+use super::types::{AnalyzerSettings, ForgeSettings, ForgeStats};
+use crate::card::{CardSettings, StatsCard};
+use crate::chart::ChartSettings;
+use crate::error::Result;
+use crate::history::{HistorySettings, Snapshot};
+use crate::services::{RenderContext, StatsService};
+use crate::template::MessageFormat;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// A forge client that can be built from its service config, everything
+/// `ForgeService` needs on top of the API calls.
+pub trait ForgeBackend: ForgeClient + Sized + 'static {
+    type Config: DeserializeOwned + Send + Sync;
+
+    /// Service name, also picks src/configs/<name>_cfg.yml.
+    const NAME: &'static str;
+
+    fn connect(config: &Self::Config) -> Result<Self>;
+
+    fn settings(config: &Self::Config) -> &ForgeSettings;
+
+    /// Whose repos are listed: a user, a workspace, a project key.
+    fn account(config: &Self::Config) -> String;
+
+    /// Name the post is about, the account unless the forge has a nicer one.
+    fn display_name(config: &Self::Config) -> String {
+        Self::account(config)
+    }
+
+    /// How the header links back to the account.
+    fn profile_link(config: &Self::Config) -> ProfileLink<'_>;
+
+    fn analyzer_settings(config: &Self::Config) -> Result<AnalyzerSettings> {
+        Self::settings(config).analyzer_settings(Self::NAME, Self::account(config))
+    }
+}
+
+/// The channel service of any forge: analyze, store for the combined post,
+/// render with the shared formatter.
+pub struct ForgeService<C>(PhantomData<fn() -> C>);
+
+impl<C> ForgeService<C> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C> Default for ForgeService<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<C: ForgeBackend> StatsService for ForgeService<C> {
+    type Config = C::Config;
+    type Stats = ForgeStats;
+
+    fn name(&self) -> &'static str {
+        C::NAME
+    }
+
+    fn message_id(&self, config: &C::Config) -> i64 {
+        C::settings(config).message_id // 0 = send new msg
+    }
+
+    async fn collect_stats(&self, config: &C::Config) -> Result<ForgeStats> {
+        log::info!("starting {} analysis for: {}", C::NAME, C::account(config));
+
+        let client = C::connect(config)?;
+        let analyzer = ForgeAnalyzer::new(client, C::analyzer_settings(config)?);
+        let stats = analyzer.analyze().await?;
+
+        if let Err(e) = store::save(C::NAME, &stats) { // kept for the combined post
+            log::warn!("failed to store {} stats: {}", C::NAME, e);
+        }
+
+        Ok(stats)
+    }
+
+    fn history_settings(&self, config: &C::Config) -> HistorySettings {
+        C::settings(config).history.clone()
+    }
+
+    fn snapshot(&self, stats: &ForgeStats) -> Option<Snapshot> {
+        Some(stats.snapshot())
+    }
+
+    fn chart_settings(&self, config: &C::Config) -> ChartSettings {
+        C::settings(config).chart.clone()
+    }
+
+    fn chart_languages(&self, stats: &ForgeStats) -> Vec<(String, f64)> {
+        stats.language_shares()
+    }
+
+    fn card_settings(&self, config: &C::Config) -> CardSettings {
+        C::settings(config).card.clone()
+    }
+
+    fn stats_card(&self, stats: &ForgeStats, config: &C::Config) -> Option<StatsCard> {
+        Some(formatter::stats_card(stats, &C::display_name(config), &C::profile_link(config)))
+    }
+
+    fn message_format(&self, config: &C::Config) -> MessageFormat {
+        let settings = C::settings(config);
+        match settings.template_path() {
+            Some(_) => settings.parse_mode,
+            None => MessageFormat::MarkdownV2, // built-in layout
+        }
+    }
+
+    fn render_message(&self, stats: &ForgeStats, config: &C::Config, context: &RenderContext) -> Result<String> {
+        formatter::render_message(stats, C::settings(config), &C::display_name(config), &C::profile_link(config), context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitbucket::BitbucketService;
+    use crate::gitea::GiteaService;
+    use crate::github::GitHubService;
+    use crate::gitlab::GitLabService;
+    use crate::local::LocalService;
+
+    fn analyzer_settings<C: ForgeBackend>(service: ForgeService<C>) -> AnalyzerSettings {
+        let config = service.load_config().unwrap();
+        C::analyzer_settings(&config).unwrap()
+    }
+
+    #[test]
+    fn shipped_configs_parse_with_the_shared_block() {
+        for settings in [
+            analyzer_settings(GitHubService::new()),
+            analyzer_settings(GitLabService::new()),
+            analyzer_settings(GiteaService::new()),
+            analyzer_settings(BitbucketService::new()),
+            analyzer_settings(LocalService::new()),
+        ] {
+            assert_eq!(settings.languages.get("rs").map(String::as_str), Some("Rust"));
+            assert!(settings.max_requests > 0);
+        }
+    }
+
+    #[test]
+    fn cache_file_is_named_after_the_service() {
+        let settings = analyzer_settings(GiteaService::new());
+        assert_eq!(settings.cache_path.unwrap().to_str(), Some("data/gitea_cache.json"));
+    }
+}
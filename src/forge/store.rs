@@ -0,0 +1,55 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::ForgeStats;
+use crate::error::Result;
+use std::fs;
+use std::path::PathBuf;
+
+// latest stats of every forge service, read by the combined summary
+fn stats_path(service: &str) -> PathBuf {
+    PathBuf::from(format!("data/stats/{}.json", service))
+}
+
+pub fn save(service: &str, stats: &ForgeStats) -> Result<()> {
+    let path = stats_path(service);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string(stats)?)?;
+    Ok(())
+}
+
+/// Stats of every service that stored some, by service name.
+pub fn load_all() -> Result<Vec<(String, ForgeStats)>> {
+    let mut all = Vec::new();
+    let entries = match fs::read_dir("data/stats") {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(all),
+        Err(e) => return Err(e.into()),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        let Some(service) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match load(service) {
+            Ok(Some(stats)) => all.push((service.to_string(), stats)),
+            Ok(None) => {}
+            Err(e) => log::warn!("ignoring broken stats file {}: {}", path.display(), e),
+        }
+    }
+
+    all.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(all)
+}
+
+/// None if the service never finished a run.
+pub fn load(service: &str) -> Result<Option<ForgeStats>> {
+    match fs::read_to_string(stats_path(service)) {
+        Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,298 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::counter::LineCounts;
+use super::detect::{self, Candidate};
+use super::filter::{PathFilter, PathRules};
+use crate::card::CardSettings;
+use crate::chart::ChartSettings;
+use crate::error::Result;
+use crate::history::{HistorySettings, Snapshot};
+use crate::template::MessageFormat;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Repository as seen by the analyzer, whatever forge it came from.
+#[derive(Debug, Clone)]
+pub struct ForgeRepo {
+    pub id: String, // whatever the client addresses the repo by
+    pub name: String,
+    pub default_branch: Option<String>,
+    pub private: bool,
+    pub last_activity: String,
+    pub url: Option<String>, // web or clone url, what mirrors of it point at
+    pub remote: Option<String>, // upstream a mirror or import was made from
+}
+
+#[derive(Debug, Clone)]
+pub struct TreeEntry {
+    pub path: String,
+    pub sha: String, // blob sha, unchanged content keeps the same one
+}
+
+/// How file contents are fetched from the forge.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalysisMode {
+    /// One request per file, cheap for small accounts.
+    #[default]
+    Api,
+    /// One tarball per repository, counted locally.
+    Archive,
+}
+
+/// Config keys every forge service shares, flattened into its own config
+/// next to the keys that say how to reach the forge.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ForgeSettings {
+    pub message_id: i64,
+    pub private_repo: bool,
+    pub quote: bool,
+    pub max_langs: usize,
+    #[serde(default)]
+    pub top_repos: TopReposSettings,
+    #[serde(default)]
+    pub analysis_mode: AnalysisMode,
+    #[serde(default = "default_incremental")]
+    pub incremental: bool,
+    #[serde(default = "default_max_requests")]
+    pub max_requests: usize,
+    #[serde(default = "default_parallel_repos")]
+    pub parallel_repos: usize,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub history: HistorySettings,
+    #[serde(default)]
+    pub chart: ChartSettings,
+    #[serde(default)]
+    pub card: CardSettings,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub parse_mode: MessageFormat,
+    #[serde(default)]
+    pub paths: PathRules,
+    pub languages: HashMap<String, String>,
+    #[serde(default)]
+    pub filenames: HashMap<String, String>,
+}
+
+fn default_incremental() -> bool {
+    true // skip repos whose head did not move
+}
+
+fn default_max_requests() -> usize {
+    8 // keep well under api limits
+}
+
+fn default_parallel_repos() -> usize {
+    4
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+impl ForgeSettings {
+    pub fn template_path(&self) -> Option<&str> {
+        self.template.as_deref().filter(|path| !path.is_empty())
+    }
+
+    /// `service` names the cache file, `username` is whose repos to list.
+    pub fn analyzer_settings(&self, service: &str, username: String) -> Result<AnalyzerSettings> {
+        Ok(AnalyzerSettings {
+            username,
+            private_repo: self.private_repo,
+            max_langs: self.max_langs,
+            mode: self.analysis_mode,
+            cache_path: self.incremental.then(|| PathBuf::from(format!("data/{}_cache.json", service))),
+            max_requests: self.max_requests,
+            parallel_repos: self.parallel_repos,
+            languages: self.languages.clone(),
+            filenames: detect::filename_map(&self.filenames),
+            filter: PathFilter::new(&self.paths)?,
+        })
+    }
+}
+
+/// The part of a service config the analyzer cares about.
+#[derive(Debug, Clone)]
+pub struct AnalyzerSettings {
+    pub username: String,
+    pub private_repo: bool,
+    pub max_langs: usize,
+    pub mode: AnalysisMode,
+    pub cache_path: Option<PathBuf>, // None = full recount every run
+    pub max_requests: usize,
+    pub parallel_repos: usize,
+    pub languages: HashMap<String, String>, // extension -> language
+    pub filenames: HashMap<String, String>, // exact file name -> language
+    pub filter: PathFilter,
+}
+
+impl AnalyzerSettings {
+    pub fn detect(&self, path: &str) -> Candidate {
+        detect::by_path(path, &self.filenames, &self.languages)
+    }
+
+    /// Everything besides the extension map that decides what counts,
+    /// cached counts made with something else are stale.
+    pub fn fingerprint(&self) -> String {
+        let mut filenames: Vec<_> = self.filenames.iter().collect();
+        filenames.sort();
+        format!("{}|{:?}", self.filter.fingerprint(), filenames)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub name: String,
+    pub percentage: f64,
+}
+
+/// Every language with its share of `total_lines`, biggest first.
+pub fn rank_languages(language_lines: &HashMap<String, usize>, total_lines: usize) -> Vec<LanguageStats> {
+    let mut ranked: Vec<LanguageStats> = language_lines
+        .iter()
+        .map(|(name, &lines)| {
+            let percentage = if total_lines > 0 {
+                (lines as f64 / total_lines as f64) * 100.0
+            } else {
+                0.0
+            };
+            LanguageStats {
+                name: name.clone(),
+                percentage,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.percentage.partial_cmp(&a.percentage).unwrap());
+    ranked
+}
+
+/// One analyzed repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoStats {
+    pub name: String,
+    pub private: bool,
+    pub last_activity: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub remote: Option<String>,
+    pub total_lines: usize, // code only
+    pub languages: HashMap<String, LineCounts>,
+}
+
+impl RepoStats {
+    pub fn new(repo: ForgeRepo, languages: HashMap<String, LineCounts>) -> Self {
+        RepoStats {
+            name: repo.name,
+            private: repo.private,
+            last_activity: repo.last_activity,
+            url: repo.url,
+            remote: repo.remote,
+            total_lines: languages.values().map(|counts| counts.code).sum(),
+            languages,
+        }
+    }
+
+    pub fn dominant_language(&self) -> Option<&str> {
+        self.languages
+            .iter()
+            .max_by_key(|(_, counts)| counts.code)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Normalized urls of the repo itself and of the upstream it was
+    /// mirrored or imported from, a copy elsewhere shares one of them.
+    pub fn mirror_urls(&self) -> impl Iterator<Item = String> + '_ {
+        self.url.iter().chain(&self.remote).map(|url| normalize_url(url))
+    }
+}
+
+/// `https://github.com/a/b.git`, `git@github.com:a/b` and
+/// `https://user@github.com/a/b/` all become `github.com/a/b`.
+pub fn normalize_url(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let url = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        None => url.replacen(':', "/", 1), // scp-like git@host:path
+    };
+    let (host, path) = url.split_once('/').unwrap_or((&url, ""));
+    let host = host.rsplit('@').next().unwrap_or(host); // credentials or ssh user
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_end_matches('/');
+    format!("{}/{}", host, path)
+}
+
+/// Per-service `top_repos` block of the config.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct TopReposSettings {
+    /// Repos listed under the languages, 0 = no section.
+    #[serde(default)]
+    pub count: usize,
+    /// Private repo names stay out of the post unless this is set.
+    #[serde(default)]
+    pub include_private: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ForgeStats {
+    pub total_lines: usize, // code lines
+    pub language_stats: Vec<LanguageStats>, // top `max_langs` only
+    pub language_lines: HashMap<String, usize>, // every language, code lines
+    #[serde(default)]
+    pub line_counts: LineCounts, // code, comments and blanks of everything
+    #[serde(default)]
+    pub language_counts: HashMap<String, LineCounts>,
+    pub total_languages: usize,
+    pub favorite_language: String,
+    pub total_repos: usize,
+    pub public_repos: usize,
+    pub last_activity: String,
+    pub skipped_files: usize, // could not be read even after retries
+    pub skipped_repos: usize,
+    #[serde(default)]
+    pub repos: Vec<RepoStats>,
+}
+
+impl ForgeStats {
+    pub fn is_incomplete(&self) -> bool {
+        self.skipped_files > 0 || self.skipped_repos > 0
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            taken_at: Utc::now().timestamp(),
+            total_lines: self.total_lines,
+            languages: self.language_lines.clone(),
+            total_repos: self.total_repos,
+            public_repos: self.public_repos,
+        }
+    }
+
+    /// Biggest repos by lines of code, see `TopReposSettings`.
+    pub fn top_repos(&self, settings: &TopReposSettings) -> Vec<&RepoStats> {
+        let mut repos: Vec<&RepoStats> = self.repos
+            .iter()
+            .filter(|repo| settings.include_private || !repo.private)
+            .filter(|repo| repo.total_lines > 0)
+            .collect();
+        repos.sort_by(|a, b| b.total_lines.cmp(&a.total_lines).then_with(|| a.name.cmp(&b.name)));
+        repos.truncate(settings.count);
+        repos
+    }
+
+    /// Shown languages as `(name, percentage)` for the chart.
+    pub fn language_shares(&self) -> Vec<(String, f64)> {
+        self.language_stats
+            .iter()
+            .map(|lang| (lang.name.clone(), lang.percentage))
+            .collect()
+    }
+}
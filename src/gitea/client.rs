@@ -0,0 +1,188 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{BranchResponse, GiteaConfig, Repository, TreeItem, TreeResponse};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::archive::response_stream;
+use crate::forge::client::{ArchiveStream, ForgeClient};
+use crate::forge::http::{with_tls, ForgeHttp};
+use crate::forge::types::{ForgeRepo, TreeEntry};
+use async_trait::async_trait;
+use reqwest::{Client, header};
+
+pub struct GiteaClient {
+    http: ForgeHttp,
+    base_url: String,
+}
+
+impl GiteaClient {
+    pub fn new(config: &GiteaConfig) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        if !config.gitea_token.is_empty() {
+            headers.insert(
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&format!("token {}", config.gitea_token))?,
+            );
+        }
+        headers.insert(
+            header::USER_AGENT,
+            header::HeaderValue::from_static("tele-stats"),
+        );
+
+        let builder = Client::builder()
+            .default_headers(headers);
+        let client = with_tls(builder, config.ca_cert.as_deref(), config.insecure_tls, config.instance_root())?
+            .build()?;
+
+        Ok(Self {
+            http: ForgeHttp::new(client, config.settings.max_retries),
+            base_url: format!("{}/api/v1", config.instance_root()),
+        })
+    }
+
+    pub async fn get_user_repos(&self, username: &str, include_private: bool) -> Result<Vec<Repository>> {
+        let mut all_repos = Vec::new();
+        let mut page = 1;
+        let limit = 50; // default MAX_RESPONSE_ITEMS of an instance
+
+        loop {
+            let url = format!(
+                "{}/users/{}/repos?page={}&limit={}",
+                self.base_url, username, page, limit
+            );
+
+            let response = self.http.get(&url).await?;
+
+            if !response.status().is_success() {
+                return Err(TeleStatsError::from_status(response.status(), "failed to fetch repositories"));
+            }
+
+            let repos: Vec<Repository> = response.json().await?;
+
+            if repos.is_empty() {
+                break;
+            }
+
+            for repo in repos {
+                if repo.empty {
+                    continue; // no branch to read yet
+                }
+                if include_private || !repo.private {
+                    all_repos.push(repo);
+                }
+            }
+
+            page += 1;
+        }
+
+        Ok(all_repos)
+    }
+
+    pub async fn get_branch_head(&self, full_name: &str, branch: &str) -> Result<String> {
+        let url = format!(
+            "{}/repos/{}/branches/{}",
+            self.base_url, full_name, urlencoding::encode(branch)
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch branch"));
+        }
+
+        let branch_response: BranchResponse = response.json().await?;
+        Ok(branch_response.commit.id)
+    }
+
+    pub async fn get_repo_tree(&self, full_name: &str, branch: &str) -> Result<Vec<TreeItem>> {
+        let mut all_items = Vec::new();
+        let mut page = 1;
+        let per_page = 1000; // recursive trees are paged, unlike github's
+
+        loop {
+            let url = format!(
+                "{}/repos/{}/git/trees/{}?recursive=true&page={}&per_page={}",
+                self.base_url, full_name, urlencoding::encode(branch), page, per_page
+            );
+
+            let response = self.http.get(&url).await?;
+
+            if !response.status().is_success() {
+                return Err(TeleStatsError::from_status(response.status(), "failed to fetch tree"));
+            }
+
+            let tree_response: TreeResponse = response.json().await?;
+            let done = !tree_response.truncated || tree_response.tree.is_empty();
+            all_items.extend(tree_response.tree);
+
+            if done {
+                break;
+            }
+
+            page += 1;
+        }
+
+        Ok(all_items)
+    }
+
+    pub async fn get_file_content(&self, full_name: &str, file_path: &str, branch: &str) -> Result<String> {
+        let encoded_path: Vec<_> = file_path.split('/').map(urlencoding::encode).collect();
+        let url = format!(
+            "{}/repos/{}/raw/{}?ref={}",
+            self.base_url, full_name, encoded_path.join("/"), urlencoding::encode(branch) // raw bytes, no base64
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch file"));
+        }
+
+        let bytes = response.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    pub async fn get_archive(&self, full_name: &str, branch: &str) -> Result<ArchiveStream> {
+        let url = format!(
+            "{}/repos/{}/archive/{}.tar.gz",
+            self.base_url, full_name, urlencoding::encode(branch)
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch archive"));
+        }
+
+        Ok(response_stream(response))
+    }
+}
+
+#[async_trait]
+impl ForgeClient for GiteaClient {
+    async fn list_repos(&self, username: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        let repos = self.get_user_repos(username, include_private).await?;
+        Ok(repos.into_iter().map(ForgeRepo::from).collect())
+    }
+
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String> {
+        self.get_branch_head(&repo.id, branch).await
+    }
+
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>> {
+        let tree = self.get_repo_tree(&repo.id, branch).await?;
+        Ok(tree
+            .into_iter()
+            .filter(|item| item.item_type == "blob")
+            .map(|item| TreeEntry { path: item.path, sha: item.sha })
+            .collect())
+    }
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String> {
+        self.get_file_content(&repo.id, path, branch).await
+    }
+
+    async fn fetch_archive(&self, repo: &ForgeRepo, branch: &str) -> Result<ArchiveStream> {
+        self.get_archive(&repo.id, branch).await
+    }
+}
@@ -0,0 +1,40 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+
+use crate::error::Result;
+use crate::forge::formatter::ProfileLink;
+use crate::forge::service::{ForgeBackend, ForgeService};
+use crate::forge::types::{ForgeSettings};
+use client::GiteaClient;
+use types::GiteaConfig;
+
+/// Gitea, Forgejo and Codeberg, they share the API.
+pub type GiteaService = ForgeService<GiteaClient>;
+
+impl ForgeBackend for GiteaClient {
+    type Config = GiteaConfig;
+
+    const NAME: &'static str = "gitea";
+
+    fn connect(config: &GiteaConfig) -> Result<Self> {
+        GiteaClient::new(config)
+    }
+
+    fn settings(config: &GiteaConfig) -> &ForgeSettings {
+        &config.settings
+    }
+
+    fn account(config: &GiteaConfig) -> String {
+        config.gitea_username.clone()
+    }
+
+    fn profile_link(config: &GiteaConfig) -> ProfileLink<'_> {
+        ProfileLink {
+            forge_name: &config.forge_name, // Codeberg, Forgejo, ...
+            url: format!("{}/{}", config.instance_root(), config.gitea_username),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::forge::types::{ForgeRepo, ForgeSettings};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GiteaConfig {
+    #[serde(default)]
+    pub gitea_token: String,
+    pub gitea_username: String,
+    #[serde(default = "default_instance_url")]
+    pub instance_url: String,
+    /// Shown in the post, the same API serves Gitea, Forgejo and Codeberg.
+    #[serde(default = "default_forge_name")]
+    pub forge_name: String,
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    #[serde(default)]
+    pub insecure_tls: bool,
+    #[serde(flatten)]
+    pub settings: ForgeSettings,
+}
+
+fn default_instance_url() -> String {
+    "https://codeberg.org".to_string()
+}
+
+fn default_forge_name() -> String {
+    "Codeberg".to_string()
+}
+
+impl GiteaConfig {
+    pub fn instance_root(&self) -> &str {
+        self.instance_url.trim_end_matches('/')
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    pub full_name: String,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    pub private: bool,
+    pub updated_at: String,
+    #[serde(default)]
+    pub empty: bool,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub original_url: Option<String>, // set on mirrors and migrated repos
+}
+
+impl From<Repository> for ForgeRepo {
+    fn from(repo: Repository) -> Self {
+        ForgeRepo {
+            id: repo.full_name, // owner/name
+            name: repo.name,
+            default_branch: repo.default_branch.filter(|branch| !branch.is_empty()),
+            private: repo.private,
+            last_activity: repo.updated_at,
+            url: repo.html_url.filter(|url| !url.is_empty()),
+            remote: repo.original_url.filter(|url| !url.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TreeItem {
+    pub path: String,
+    pub sha: String,
+    #[serde(rename = "type")]
+    pub item_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TreeResponse {
+    #[serde(default)]
+    pub tree: Vec<TreeItem>,
+    #[serde(default)]
+    pub truncated: bool, // more pages follow
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BranchCommit {
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BranchResponse {
+    pub commit: BranchCommit,
+}
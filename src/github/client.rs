@@ -1,21 +1,26 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
-use super::types::{Repository, TreeResponse, ContentResponse};
-use reqwest::{Client, header};
-use std::error::Error;
+use super::types::{BranchResponse, GitHubConfig, Repository, TreeItem, TreeResponse, ContentResponse};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::archive::response_stream;
+use crate::forge::client::{ArchiveStream, ForgeClient};
+use crate::forge::http::ForgeHttp;
+use crate::forge::types::{ForgeRepo, TreeEntry};
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode, header};
 
 pub struct GitHubClient {
-    client: Client,
+    http: ForgeHttp,
     base_url: String,
 }
 
 impl GitHubClient {
-    pub fn new(token: String) -> Result<Self, Box<dyn Error>> {
+    pub fn new(config: &GitHubConfig) -> Result<Self> {
         let mut headers = header::HeaderMap::new();
         headers.insert(
             header::AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("Bearer {}", token))?,
+            header::HeaderValue::from_str(&format!("Bearer {}", config.github_token))?,
         );
         headers.insert(
             header::USER_AGENT,
@@ -31,12 +36,12 @@ impl GitHubClient {
             .build()?;
 
         Ok(Self {
-            client,
-            base_url: "https://api.github.com".to_string(),
+            http: ForgeHttp::new(client, config.settings.max_retries),
+            base_url: config.api_root().to_string(), // api.github.com or <ghes>/api/v3
         })
     }
 
-    pub async fn get_user_repos(&self, username: &str, include_private: bool) -> Result<Vec<Repository>, Box<dyn Error>> {
+    pub async fn get_user_repos(&self, include_private: bool) -> Result<Vec<Repository>> {
         let mut all_repos = Vec::new();
         let mut page = 1;
         let per_page = 100;
@@ -47,10 +52,10 @@ impl GitHubClient {
                 self.base_url, page, per_page
             );
 
-            let response = self.client.get(&url).send().await?;
+            let response = self.http.get(&url).await?;
             
             if !response.status().is_success() {
-                return Err(format!("failed to fetch repositories: {}", response.status()).into());
+                return Err(TeleStatsError::from_status(response.status(), "failed to fetch repositories"));
             }
 
             let repos: Vec<Repository> = response.json().await?;
@@ -71,42 +76,107 @@ impl GitHubClient {
         Ok(all_repos)
     }
 
-    pub async fn get_repo_tree(&self, owner: &str, repo: &str, branch: &str) -> Result<Vec<super::types::TreeItem>, Box<dyn Error>> {
+    pub async fn get_branch_head(&self, full_name: &str, branch: &str) -> Result<String> {
         let url = format!(
-            "{}/repos/{}/{}/git/trees/{}?recursive=1",
-            self.base_url, owner, repo, branch
+            "{}/repos/{}/branches/{}",
+            self.base_url, full_name, urlencoding::encode(branch)
         );
 
-        let response = self.client.get(&url).send().await?;
-        
+        let response = self.http.get(&url).await?;
+
         if !response.status().is_success() {
-            return Err(format!("failed to fetch tree: {}", response.status()).into());
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch branch"));
+        }
+
+        let branch_response: BranchResponse = response.json().await?;
+        Ok(branch_response.commit.sha)
+    }
+
+    pub async fn get_repo_tree(&self, full_name: &str, branch: &str) -> Result<Vec<TreeItem>> {
+        let url = format!(
+            "{}/repos/{}/git/trees/{}?recursive=1",
+            self.base_url, full_name, urlencoding::encode(branch)
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if response.status() == StatusCode::CONFLICT {
+            return Ok(Vec::new()); // "Git Repository is empty"
+        }
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch tree"));
         }
 
         let tree_response: TreeResponse = response.json().await?;
         Ok(tree_response.tree)
     }
 
-    pub async fn get_file_content(&self, owner: &str, repo: &str, file_path: &str, branch: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn get_file_content(&self, full_name: &str, file_path: &str, branch: &str) -> Result<String> {
+        let encoded_path: Vec<_> = file_path.split('/').map(urlencoding::encode).collect();
         let url = format!(
-            "{}/repos/{}/{}/contents/{}?ref={}",
-            self.base_url, owner, repo, file_path, branch
+            "{}/repos/{}/contents/{}?ref={}",
+            self.base_url, full_name, encoded_path.join("/"), urlencoding::encode(branch)
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.get(&url).await?;
         
         if !response.status().is_success() {
-            return Err(format!("failed to fetch file: {}", response.status()).into());
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch file"));
         }
 
         let content_response: ContentResponse = response.json().await?;
         
         if content_response.encoding == "base64" {
             use base64::{Engine as _, engine::general_purpose};
-            let decoded = general_purpose::STANDARD.decode(&content_response.content.replace("\n", ""))?;
+            let decoded = general_purpose::STANDARD.decode(content_response.content.replace("\n", ""))?;
             Ok(String::from_utf8_lossy(&decoded).to_string())
         } else {
-            Err("unsupported encoding".into())
+            Err(TeleStatsError::Decode(format!("file {}: unsupported encoding {}", file_path, content_response.encoding)))
         }
     }
-}
\ No newline at end of file
+
+    pub async fn get_archive(&self, full_name: &str, branch: &str) -> Result<ArchiveStream> {
+        let url = format!(
+            "{}/repos/{}/tarball/{}",
+            self.base_url, full_name, urlencoding::encode(branch)
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch archive"));
+        }
+
+        Ok(response_stream(response))
+    }
+}
+
+#[async_trait]
+impl ForgeClient for GitHubClient {
+    async fn list_repos(&self, _username: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        // /user/repos is scoped to the token owner, the username is only used for display
+        let repos = self.get_user_repos(include_private).await?;
+        Ok(repos.into_iter().map(ForgeRepo::from).collect())
+    }
+
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String> {
+        self.get_branch_head(&repo.id, branch).await
+    }
+
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>> {
+        let tree = self.get_repo_tree(&repo.id, branch).await?;
+        Ok(tree
+            .into_iter()
+            .filter(|item| item.item_type == "blob")
+            .map(|item| TreeEntry { path: item.path, sha: item.sha })
+            .collect())
+    }
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String> {
+        self.get_file_content(&repo.id, path, branch).await
+    }
+
+    async fn fetch_archive(&self, repo: &ForgeRepo, branch: &str) -> Result<ArchiveStream> {
+        self.get_archive(&repo.id, branch).await
+    }
+}
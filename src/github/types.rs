@@ -1,32 +1,70 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
+use crate::forge::types::{ForgeRepo, ForgeSettings};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GitHubConfig {
     pub github_token: String,
-    pub message_id: i64,
-    pub private_repo: bool,
-    pub quote: bool,
-    pub max_langs: usize,
     pub github_username: String,
-    pub languages: HashMap<String, String>,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default = "default_web_url")]
+    pub web_url: String,
+    #[serde(flatten)]
+    pub settings: ForgeSettings,
+}
+
+fn default_api_url() -> String {
+    "https://api.github.com".to_string()
+}
+
+fn default_web_url() -> String {
+    "https://github.com".to_string()
+}
+
+impl GitHubConfig {
+    pub fn api_root(&self) -> &str {
+        self.api_url.trim_end_matches('/')
+    }
+
+    pub fn web_root(&self) -> &str {
+        self.web_url.trim_end_matches('/')
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Repository {
-    pub id: u64,
     pub name: String,
+    pub full_name: String,
     pub default_branch: String,
     pub private: bool,
     pub updated_at: String,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub mirror_url: Option<String>,
+}
+
+impl From<Repository> for ForgeRepo {
+    fn from(repo: Repository) -> Self {
+        ForgeRepo {
+            id: repo.full_name,
+            name: repo.name,
+            default_branch: Some(repo.default_branch),
+            private: repo.private,
+            last_activity: repo.updated_at,
+            url: repo.html_url,
+            remote: repo.mirror_url,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TreeItem {
     pub path: String,
+    pub sha: String,
     #[serde(rename = "type")]
     pub item_type: String,
 }
@@ -36,25 +74,18 @@ pub struct TreeResponse {
     pub tree: Vec<TreeItem>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BranchCommit {
+    pub sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BranchResponse {
+    pub commit: BranchCommit,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ContentResponse {
     pub content: String,
     pub encoding: String,
 }
-
-#[derive(Debug, Clone)]
-pub struct LanguageStats {
-    pub name: String,
-    pub percentage: f64,
-}
-
-#[derive(Debug)]
-pub struct GitHubStats {
-    pub total_lines: usize,
-    pub language_stats: Vec<LanguageStats>,
-    pub total_languages: usize,
-    pub favorite_language: String,
-    pub total_repos: usize,
-    pub public_repos: usize,
-    pub last_activity: String,
-}
\ No newline at end of file
@@ -1,48 +1,54 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
-use super::types::{Repository, TreeItem}; // repo and tree structs
+use super::types::{Branch, GitLabConfig, Repository, TreeItem}; // cfg, repo and tree structs
+use crate::error::{Result, TeleStatsError};
+use crate::forge::archive::response_stream;
+use crate::forge::client::{ArchiveStream, ForgeClient}; // common forge api
+use crate::forge::http::{with_tls, ForgeHttp};
+use crate::forge::types::{ForgeRepo, TreeEntry};
+use async_trait::async_trait;
 use reqwest::{Client, header}; // http client
-use std::error::Error; // err trait
 
 pub struct GitLabClient {
-    client: Client, // http client inst
+    http: ForgeHttp, // rate-limit aware http client
     base_url: String, // gl api base url
 }
 
 impl GitLabClient {
-    pub fn new(token: String) -> Result<Self, Box<dyn Error>> {
+    pub fn new(config: &GitLabConfig) -> Result<Self> {
         let mut headers = header::HeaderMap::new(); // req headers
         headers.insert(
             "PRIVATE-TOKEN",
-            header::HeaderValue::from_str(&token)?, // auth token header
+            header::HeaderValue::from_str(&config.gitlab_token)?, // auth token header
         );
 
-        let client = Client::builder()
-            .default_headers(headers) // attach auth headers
+        let builder = Client::builder()
+            .default_headers(headers); // attach auth headers
+        let client = with_tls(builder, config.ca_cert.as_deref(), config.insecure_tls, config.instance_root())?
             .build()?; // build client
 
         Ok(Self {
-            client,
-            base_url: "https://gitlab.com/api/v4".to_string(), // gl api root
+            http: ForgeHttp::new(client, config.settings.max_retries),
+            base_url: format!("{}/api/v4", config.instance_root()), // gl api root
         })
     }
 
-    pub async fn get_user_repos(&self, username: &str, include_private: bool) -> Result<Vec<Repository>, Box<dyn Error>> {
+    pub async fn get_user_repos(&self, username: &str, include_private: bool) -> Result<Vec<Repository>> {
         let mut all_repos = Vec::new(); // collected repos
         let mut page = 1; // pagination index
         let per_page = 100; // items per page
 
         loop {
             let url = format!(
-                "{}/users/{}/projects?page={}&per_page={}&simple=true",
+                "{}/users/{}/projects?page={}&per_page={}",
                 self.base_url, username, page, per_page // repos fetch url
             );
 
-            let response = self.client.get(&url).send().await?; // exec request
+            let response = self.http.get(&url).await?; // exec request
             
             if !response.status().is_success() {
-                return Err(format!("failed to fetch repositories: {}", response.status()).into()); // request failed
+                return Err(TeleStatsError::from_status(response.status(), "failed to fetch repositories")); // request failed
             }
 
             let repos: Vec<Repository> = response.json().await?; // decode json
@@ -63,7 +69,23 @@ impl GitLabClient {
         Ok(all_repos)
     }
 
-    pub async fn get_repo_tree(&self, project_id: u64, branch: &str) -> Result<Vec<TreeItem>, Box<dyn Error>> {
+    pub async fn get_branch_head(&self, project_id: &str, branch: &str) -> Result<String> {
+        let url = format!(
+            "{}/projects/{}/repository/branches/{}",
+            self.base_url, project_id, urlencoding::encode(branch) // branch info url
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch branch"));
+        }
+
+        let branch: Branch = response.json().await?;
+        Ok(branch.commit.id) // head commit sha
+    }
+
+    pub async fn get_repo_tree(&self, project_id: &str, branch: &str) -> Result<Vec<TreeItem>> {
         let mut all_items = Vec::new(); // store all tree items
         let mut page = 1;
         let per_page = 100;
@@ -71,13 +93,13 @@ impl GitLabClient {
         loop {
             let url = format!(
                 "{}/projects/{}/repository/tree?ref={}&recursive=true&page={}&per_page={}",
-                self.base_url, project_id, branch, page, per_page // repo tree url
+                self.base_url, project_id, urlencoding::encode(branch), page, per_page // repo tree url
             );
 
-            let response = self.client.get(&url).send().await?;
+            let response = self.http.get(&url).await?;
             
             if !response.status().is_success() {
-                return Err(format!("failed to fetch tree: {}", response.status()).into()); // request failure
+                return Err(TeleStatsError::from_status(response.status(), "failed to fetch tree")); // request failure
             }
 
             let items: Vec<TreeItem> = response.json().await?;
@@ -93,17 +115,17 @@ impl GitLabClient {
         Ok(all_items)
     }
 
-    pub async fn get_file_content(&self, project_id: u64, file_path: &str, branch: &str) -> Result<String, Box<dyn Error>> {
+    pub async fn get_file_content(&self, project_id: &str, file_path: &str, branch: &str) -> Result<String> {
         let encoded_path = urlencoding::encode(file_path); // url-safe path
         let url = format!(
             "{}/projects/{}/repository/files/{}?ref={}",
-            self.base_url, project_id, encoded_path, branch // file content url
+            self.base_url, project_id, encoded_path, urlencoding::encode(branch) // file content url
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.http.get(&url).await?;
         
         if !response.status().is_success() {
-            return Err(format!("failed to fetch file: {}", response.status()).into()); // file request fail
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch file")); // file request fail
         }
 
         let json: serde_json::Value = response.json().await?; // decode json
@@ -113,7 +135,51 @@ impl GitLabClient {
             let decoded = general_purpose::STANDARD.decode(content)?; // decode base64
             Ok(String::from_utf8_lossy(&decoded).to_string()) // convert to utf8 string
         } else {
-            Err("no content in response".into()) // missing file data
+            Err(TeleStatsError::Decode(format!("file {}: no content in response", file_path))) // missing file data
+        }
+    }
+
+    pub async fn get_archive(&self, project_id: &str, branch: &str) -> Result<ArchiveStream> {
+        let url = format!(
+            "{}/projects/{}/repository/archive.tar.gz?sha={}",
+            self.base_url, project_id, urlencoding::encode(branch) // whole branch in one tarball
+        );
+
+        let response = self.http.get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(TeleStatsError::from_status(response.status(), "failed to fetch archive"));
         }
+
+        Ok(response_stream(response)) // streamed, not buffered
+    }
+}
+
+#[async_trait]
+impl ForgeClient for GitLabClient {
+    async fn list_repos(&self, username: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        let repos = self.get_user_repos(username, include_private).await?;
+        Ok(repos.into_iter().map(ForgeRepo::from).collect())
+    }
+
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String> {
+        self.get_branch_head(&repo.id, branch).await
     }
-}
\ No newline at end of file
+
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>> {
+        let tree = self.get_repo_tree(&repo.id, branch).await?;
+        Ok(tree
+            .into_iter()
+            .filter(|item| item.item_type == "blob") // skip non-files
+            .map(|item| TreeEntry { path: item.path, sha: item.id })
+            .collect())
+    }
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String> {
+        self.get_file_content(&repo.id, path, branch).await
+    }
+
+    async fn fetch_archive(&self, repo: &ForgeRepo, branch: &str) -> Result<ArchiveStream> {
+        self.get_archive(&repo.id, branch).await
+    }
+}
@@ -1,18 +1,31 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
+use crate::forge::types::{ForgeRepo, ForgeSettings};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 // config parsing, nothing interesting
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GitLabConfig {
     pub gitlab_token: String,
-    pub message_id: i64,
-    pub private_repo: bool,
-    pub quote: bool,
-    pub max_langs: usize,
     pub gitlab_username: String,
-    pub languages: HashMap<String, String>,
+    #[serde(default = "default_instance_url")]
+    pub instance_url: String,
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    #[serde(default)]
+    pub insecure_tls: bool,
+    #[serde(flatten)]
+    pub settings: ForgeSettings, // message, analysis and rendering keys
+}
+
+fn default_instance_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+impl GitLabConfig {
+    pub fn instance_root(&self) -> &str {
+        self.instance_url.trim_end_matches('/') // tolerate trailing slash in cfg
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,28 +35,40 @@ pub struct Repository {
     pub default_branch: Option<String>,
     pub visibility: String,
     pub last_activity_at: String,
+    #[serde(default)]
+    pub web_url: Option<String>,
+    #[serde(default)]
+    pub import_url: Option<String>, // only shown to maintainers
+}
+
+impl From<Repository> for ForgeRepo {
+    fn from(repo: Repository) -> Self {
+        ForgeRepo {
+            id: repo.id.to_string(), // projects are addressed by numeric id
+            private: repo.visibility != "public",
+            name: repo.name,
+            default_branch: repo.default_branch,
+            last_activity: repo.last_activity_at,
+            url: repo.web_url,
+            remote: repo.import_url,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TreeItem {
+    pub id: String, // blob sha
     pub path: String,
     #[serde(rename = "type")]
     pub item_type: String,
 }
 
-#[derive(Debug, Clone)]
-pub struct LanguageStats {
-    pub name: String,
-    pub percentage: f64,
-}
-
-#[derive(Debug)]
-pub struct GitLabStats {
-    pub total_lines: usize,
-    pub language_stats: Vec<LanguageStats>,
-    pub total_languages: usize,
-    pub favorite_language: String,
-    pub total_repos: usize,
-    pub public_repos: usize,
-    pub last_activity: String,
-}
\ No newline at end of file
+#[derive(Debug, Deserialize)]
+pub struct BranchCommit {
+    pub id: String, // commit sha
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Branch {
+    pub commit: BranchCommit,
+}
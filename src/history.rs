@@ -0,0 +1,154 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::Result;
+use chrono::{Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const HISTORY_PATH: &str = "data/history.sqlite3";
+
+/// Per-service `history` block of the config.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct HistorySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Compare with the last snapshot at least this old, 0 = previous run.
+    #[serde(default)]
+    pub compare_days: u32,
+}
+
+impl HistorySettings {
+    /// Suffix for deltas in the message, e.g. "this week".
+    pub fn period_label(&self) -> String {
+        match self.compare_days {
+            0 => "since last update".to_string(),
+            1 => "today".to_string(),
+            7 => "this week".to_string(),
+            30 => "this month".to_string(),
+            days => format!("in {} days", days),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub taken_at: i64, // unix seconds
+    pub total_lines: usize,
+    pub languages: HashMap<String, usize>,
+    pub total_repos: usize,
+    pub public_repos: usize,
+}
+
+impl Snapshot {
+    pub fn language_share(&self, language: &str) -> Option<f64> {
+        let lines = *self.languages.get(language)?;
+        if self.total_lines == 0 {
+            return None;
+        }
+        Some(lines as f64 / self.total_lines as f64 * 100.0)
+    }
+}
+
+/// Every run's numbers, kept in a local SQLite file so posts can show trends.
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open() -> Result<Self> {
+        if let Some(dir) = Path::new(HISTORY_PATH).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let conn = Connection::open(HISTORY_PATH)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                id INTEGER PRIMARY KEY,
+                service TEXT NOT NULL,
+                taken_at INTEGER NOT NULL,
+                total_lines INTEGER NOT NULL,
+                total_repos INTEGER NOT NULL,
+                public_repos INTEGER NOT NULL,
+                languages TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS snapshots_service_time ON snapshots (service, taken_at);",
+        )?;
+
+        Ok(Self { conn })
+    }
+
+    pub fn record(&self, service: &str, snapshot: &Snapshot) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO snapshots (service, taken_at, total_lines, total_repos, public_repos, languages)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                service,
+                snapshot.taken_at,
+                snapshot.total_lines as i64,
+                snapshot.total_repos as i64,
+                snapshot.public_repos as i64,
+                serde_json::to_string(&snapshot.languages)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Snapshot to compare a new run against, see `HistorySettings::compare_days`.
+    pub fn baseline(&self, service: &str, compare_days: u32) -> Result<Option<Snapshot>> {
+        let cutoff = if compare_days == 0 {
+            i64::MAX
+        } else {
+            (Utc::now() - Duration::days(compare_days as i64)).timestamp()
+        };
+
+        let row = self.conn
+            .query_row(
+                "SELECT taken_at, total_lines, total_repos, public_repos, languages FROM snapshots
+                 WHERE service = ?1 AND taken_at <= ?2
+                 ORDER BY taken_at DESC LIMIT 1",
+                params![service, cutoff],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        let Some((taken_at, total_lines, total_repos, public_repos, languages)) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(Snapshot {
+            taken_at,
+            total_lines: total_lines as usize,
+            languages: serde_json::from_str(&languages)?,
+            total_repos: total_repos as usize,
+            public_repos: public_repos as usize,
+        }))
+    }
+
+    /// `(taken_at, total_lines)` of the last `days` days, oldest first.
+    pub fn series(&self, service: &str, days: u32) -> Result<Vec<(i64, usize)>> {
+        let since = (Utc::now() - Duration::days(days as i64)).timestamp();
+        let mut stmt = self.conn.prepare(
+            "SELECT taken_at, total_lines FROM snapshots
+             WHERE service = ?1 AND taken_at >= ?2
+             ORDER BY taken_at",
+        )?;
+
+        let rows = stmt.query_map(params![service, since], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+
+        Ok(rows.collect::<std::result::Result<_, _>>()?)
+    }
+}
@@ -0,0 +1,314 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use super::types::{LocalConfig, LocalRepo};
+use crate::error::{Result, TeleStatsError};
+use crate::forge::client::ForgeClient;
+use crate::forge::types::{ForgeRepo, TreeEntry};
+use async_trait::async_trait;
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tokio::process::Command;
+
+/// Reads repositories straight from disk: the working tree of a directory,
+/// or the blobs of a git repository at a ref. The branch the analyzer
+/// passes around is that ref, "" = working tree.
+pub struct LocalClient {
+    repos: Vec<LocalRepo>,
+}
+
+impl LocalClient {
+    pub fn new(config: &LocalConfig) -> Self {
+        Self {
+            repos: config.repos.clone(),
+        }
+    }
+
+    async fn describe(&self, repo: &LocalRepo) -> ForgeRepo {
+        let git_ref = repo.git_ref();
+        let revision = git_ref.as_deref().unwrap_or("HEAD");
+
+        let last_activity = match git(&repo.path, &["log", "-1", "--format=%cI", revision]).await {
+            Ok(date) => String::from_utf8_lossy(&date).trim().to_string(),
+            Err(_) => modified(&repo.path).await, // not a git checkout
+        };
+        let remote = git(&repo.path, &["remote", "get-url", "origin"]).await
+            .map(|url| String::from_utf8_lossy(&url).trim().to_string())
+            .ok()
+            .filter(|url| !url.is_empty()); // lets the combined post spot forge clones
+
+        ForgeRepo {
+            id: repo.path.clone(),
+            name: repo.display_name(),
+            default_branch: Some(git_ref.unwrap_or_default()),
+            private: repo.private,
+            last_activity,
+            url: None, // only known by its path
+            remote,
+        }
+    }
+}
+
+async fn git(dir: &str, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(TeleStatsError::Git(format!(
+            "git {} in {}: {}",
+            args.join(" "), dir, String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+async fn modified(path: &str) -> String {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Files of a working tree, skipping whatever `.gitignore` and friends ignore.
+fn walk(root: &Path) -> Vec<TreeEntry> {
+    let walker = WalkBuilder::new(root)
+        .hidden(false) // .gitattributes and dotfiles are left to the filter
+        .require_git(false) // .gitignore counts in plain directories too
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+
+    let mut entries = Vec::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!("skipping unreadable path in {}: {}", root.display(), e);
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+
+        // stands in for a blob sha, a file with the same size and mtime keeps its cached counts
+        let sha = entry.metadata()
+            .ok()
+            .and_then(|metadata| {
+                let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+                Some(format!("{}-{}", metadata.len(), modified.as_nanos()))
+            })
+            .unwrap_or_default();
+
+        entries.push(TreeEntry {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            sha,
+        });
+    }
+
+    entries
+}
+
+#[async_trait]
+impl ForgeClient for LocalClient {
+    async fn list_repos(&self, _username: &str, include_private: bool) -> Result<Vec<ForgeRepo>> {
+        let mut repos = Vec::new();
+        for repo in self.repos.iter().filter(|repo| include_private || !repo.private) {
+            repos.push(self.describe(repo).await);
+        }
+        Ok(repos)
+    }
+
+    async fn head_commit(&self, repo: &ForgeRepo, branch: &str) -> Result<String> {
+        if branch.is_empty() {
+            return Ok(String::new()); // a working tree has no fixed head, files are compared one by one
+        }
+
+        let sha = git(&repo.id, &["rev-parse", "--verify", &format!("{}^{{commit}}", branch)]).await?;
+        Ok(String::from_utf8_lossy(&sha).trim().to_string())
+    }
+
+    async fn list_tree(&self, repo: &ForgeRepo, branch: &str) -> Result<Vec<TreeEntry>> {
+        if branch.is_empty() {
+            let root = PathBuf::from(&repo.id);
+            if !root.is_dir() {
+                return Err(TeleStatsError::NotFound(format!("directory {}", repo.id)));
+            }
+            return Ok(tokio::task::spawn_blocking(move || walk(&root)).await?);
+        }
+
+        // "<mode> <type> <sha>\t<path>\0" per entry
+        let listing = git(&repo.id, &["ls-tree", "-r", "-z", "--full-tree", branch]).await?;
+        Ok(listing
+            .split(|byte| *byte == 0)
+            .filter_map(|entry| {
+                let entry = String::from_utf8_lossy(entry);
+                let (meta, path) = entry.split_once('\t')?;
+                let mut fields = meta.split(' ');
+                let mode = fields.next()?;
+                let kind = fields.next()?;
+                let sha = fields.next()?;
+                // submodules are commits, symlinks only point at other files
+                (kind == "blob" && mode != "120000").then(|| TreeEntry {
+                    path: path.to_string(),
+                    sha: sha.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_file(&self, repo: &ForgeRepo, path: &str, branch: &str) -> Result<String> {
+        let content = if branch.is_empty() {
+            tokio::fs::read(Path::new(&repo.id).join(path)).await?
+        } else {
+            git(&repo.id, &["cat-file", "blob", &format!("{}:{}", branch, path)]).await?
+        };
+        Ok(String::from_utf8_lossy(&content).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::analyzer::ForgeAnalyzer;
+    use crate::forge::filter::{PathFilter, PathRules};
+    use crate::forge::types::{AnalysisMode, AnalyzerSettings, ForgeStats};
+    use std::collections::HashMap;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    /// Fresh directory under the system temp dir, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tele-stats-local-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, path: &str, content: &str) -> PathBuf {
+            let path = self.0.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            path
+        }
+
+        fn git(&self, args: &[&str]) {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&self.0)
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn settings(cache_path: Option<PathBuf>) -> AnalyzerSettings {
+        let languages = [("rs", "Rust"), ("py", "Python"), ("js", "JavaScript")]
+            .into_iter()
+            .map(|(ext, lang)| (ext.to_string(), lang.to_string()))
+            .collect();
+        AnalyzerSettings {
+            username: "someone".to_string(),
+            private_repo: true,
+            max_langs: 10,
+            mode: AnalysisMode::Api,
+            cache_path,
+            max_requests: 4,
+            parallel_repos: 1,
+            languages,
+            filenames: HashMap::new(),
+            filter: PathFilter::new(&PathRules::default()).unwrap(),
+        }
+    }
+
+    async fn analyze(dir: &TempDir, git_ref: &str, settings: AnalyzerSettings) -> ForgeStats {
+        let repo = LocalRepo {
+            path: dir.0.to_string_lossy().to_string(),
+            name: String::new(),
+            git_ref: git_ref.to_string(),
+            private: false,
+        };
+        let client = LocalClient { repos: vec![repo] };
+        ForgeAnalyzer::new(client, settings).analyze().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn plain_directories_count_their_files() {
+        let dir = TempDir::new("plain");
+        dir.write("src/main.rs", "// entry\nfn main() {\n}\n");
+        dir.write("tools/gen.py", "print(1)\n");
+        dir.write("node_modules/dep/index.js", "module.exports = 1;\n");
+        dir.write("notes.txt", "not code\n");
+        dir.write(".gitignore", "scratch/\n");
+        dir.write("scratch/tmp.py", "x = 1\n");
+
+        let stats = analyze(&dir, "", settings(None)).await;
+        assert_eq!(stats.language_lines, HashMap::from([("Rust".to_string(), 2), ("Python".to_string(), 1)]));
+        assert_eq!(stats.language_counts["Rust"].comments, 1);
+        assert_eq!(stats.total_lines, 3);
+        assert_eq!(stats.skipped_files, 0);
+    }
+
+    #[tokio::test]
+    async fn a_ref_reads_committed_blobs_not_the_working_tree() {
+        let dir = TempDir::new("git");
+        dir.git(&["init", "-q"]);
+        dir.write("lib.rs", "fn a() {}\n");
+        dir.git(&["add", "."]);
+        dir.git(&["commit", "-q", "-m", "init"]);
+        dir.write("lib.rs", "fn a() {}\nfn b() {}\n");
+        dir.write("extra.py", "x = 1\n");
+
+        let committed = analyze(&dir, "HEAD", settings(None)).await;
+        assert_eq!(committed.language_lines, HashMap::from([("Rust".to_string(), 1)]));
+
+        let working_tree = analyze(&dir, "", settings(None)).await;
+        assert_eq!(working_tree.language_lines, HashMap::from([("Rust".to_string(), 2), ("Python".to_string(), 1)]));
+    }
+
+    #[tokio::test]
+    async fn unchanged_size_and_mtime_reuse_the_cached_counts() {
+        let dir = TempDir::new("cache");
+        let cache = TempDir::new("cache-file");
+        let cache_path = cache.0.join("local_cache.json");
+        let file = dir.write("lib.rs", "fn a() {}\n");
+        let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        fs::File::options().write(true).open(&file).unwrap().set_modified(mtime).unwrap();
+
+        let first = analyze(&dir, "", settings(Some(cache_path.clone()))).await;
+        assert_eq!(first.language_counts["Rust"].code, 1);
+
+        // same size and mtime: the cache is trusted, the new content not read
+        fs::write(&file, "// a() {}\n").unwrap();
+        fs::File::options().write(true).open(&file).unwrap().set_modified(mtime).unwrap();
+        let cached = analyze(&dir, "", settings(Some(cache_path.clone()))).await;
+        assert_eq!(cached.language_counts["Rust"].code, 1);
+
+        // a new mtime changes the stand-in sha, the file is counted again
+        fs::File::options().write(true).open(&file).unwrap().set_modified(mtime + Duration::from_secs(1)).unwrap();
+        let recounted = analyze(&dir, "", settings(Some(cache_path))).await;
+        assert_eq!(recounted.language_counts["Rust"].comments, 1);
+        assert_eq!(recounted.language_counts["Rust"].code, 0);
+    }
+}
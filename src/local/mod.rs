@@ -0,0 +1,46 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+pub mod types;
+pub mod client;
+
+use crate::error::Result;
+use crate::forge::formatter::ProfileLink;
+use crate::forge::service::{ForgeBackend, ForgeService};
+use crate::forge::types::{AnalysisMode, AnalyzerSettings, ForgeSettings};
+use client::LocalClient;
+use types::LocalConfig;
+
+/// Directories and git repositories on this machine, counted like a forge.
+pub type LocalService = ForgeService<LocalClient>;
+
+impl ForgeBackend for LocalClient {
+    type Config = LocalConfig;
+
+    const NAME: &'static str = "local";
+
+    fn connect(config: &LocalConfig) -> Result<Self> {
+        Ok(LocalClient::new(config))
+    }
+
+    fn settings(config: &LocalConfig) -> &ForgeSettings {
+        &config.settings
+    }
+
+    fn account(config: &LocalConfig) -> String {
+        config.username.clone()
+    }
+
+    fn profile_link(config: &LocalConfig) -> ProfileLink<'_> {
+        ProfileLink {
+            forge_name: &config.forge_name,
+            url: config.profile_url.clone(), // "" = no link
+        }
+    }
+
+    fn analyzer_settings(config: &LocalConfig) -> Result<AnalyzerSettings> {
+        let mut settings = config.settings.analyzer_settings(Self::NAME, config.username.clone())?;
+        settings.mode = AnalysisMode::Api; // files are read one by one either way
+        Ok(settings)
+    }
+}
@@ -0,0 +1,60 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::forge::types::ForgeSettings;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LocalConfig {
+    /// Name shown in the post, there is no account behind local repos.
+    pub username: String,
+    #[serde(default = "default_forge_name")]
+    pub forge_name: String,
+    #[serde(default)]
+    pub profile_url: String,
+    pub repos: Vec<LocalRepo>,
+    #[serde(flatten)]
+    pub settings: ForgeSettings,
+}
+
+/// One directory or git repository from the `repos` list.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LocalRepo {
+    pub path: String,
+    /// "" = the directory name.
+    #[serde(default)]
+    pub name: String,
+    /// Read blobs at this ref instead of the working tree, "" = working tree
+    /// (`HEAD` for bare repos).
+    #[serde(default, rename = "ref")]
+    pub git_ref: String,
+    #[serde(default)]
+    pub private: bool,
+}
+
+impl LocalRepo {
+    pub fn display_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+        Path::new(&self.path)
+            .file_name()
+            .map(|name| name.to_string_lossy().trim_end_matches(".git").to_string())
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    /// Ref to read, None = walk the working tree.
+    pub fn git_ref(&self) -> Option<String> {
+        if !self.git_ref.is_empty() {
+            return Some(self.git_ref.clone());
+        }
+        let root = Path::new(&self.path);
+        let bare = root.join("HEAD").is_file() && root.join("objects").is_dir(); // nothing checked out
+        bare.then(|| "HEAD".to_string())
+    }
+}
+
+fn default_forge_name() -> String {
+    "Local".to_string()
+}
@@ -3,7 +3,6 @@
   
 use chrono::Local;
 use std::io::{self, Write};
-use std::process;
 // the comments were eaten by AI when I asked for something to be corrected
 pub fn init() {  
     env_logger::Builder::from_default_env()  
@@ -45,28 +44,27 @@ pub fn update_completed(service: &str) {
   
 pub fn update_failed(service: &str, error: &str) {  
     log::error!("{} service update failed: {}", service, error);
-    handle_error();
 }
 
 pub fn error_with_message(message: &str) {
     log::error!("{}", message);
-    handle_error();
+    println!("\nAn unexpected error occurred, please report it to issues: https://github.com/shareui/tele-stats/issues");
 }
 
-fn handle_error() {
+// blocking, call through spawn_blocking and only with a tty attached
+pub fn prompt_continue() -> bool {
     println!("\nAn unexpected error occurred, please report it to issues: https://github.com/shareui/tele-stats/issues");
     
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    
     print!("Continue runtime? [y/N]: ");
-    io::stdout().flush().unwrap();
+    if io::stdout().flush().is_err() {
+        return true;
+    }
     
     let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
+    if io::stdin().read_line(&mut input).is_err() {
+        return true;
+    }
     
     let response = input.trim().to_lowercase();
-    if response != "y" && response != "yes" {
-        log::info!("shutting down bot by user request");
-        process::exit(0);
-    }
+    response == "y" || response == "yes"
 }
\ No newline at end of file
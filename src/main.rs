@@ -1,13 +1,32 @@
 // MIT License
 // Copyright (c) 2025 shareui
 
+mod bitbucket;
+mod card;
+mod chart;
+mod combined;
 mod commands;
+mod dota;
+mod error;
+mod faceit;
+mod failure;
+mod forge;
 mod logs;
+mod gitea;
 mod gitlab;
+mod history;
 mod github;
+mod local;
+mod services;
+mod state;
+mod template;
 
+use card::CardServer;
 use commands::{Command, handle_command};
+use error::{ErrorAction, Result, TeleStatsError};
+use failure::FailurePolicy;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::sync::Arc;
 use teloxide::prelude::*;
@@ -19,17 +38,11 @@ struct GlobalConfig {
     update_time: u64,
     channel_id: i64,
     tg_username: String,
-    ignore_errors: bool,
-    services: Services,
-}
-
-#[derive(Debug, Deserialize, Clone)]
-struct Services {
-    gitlab: bool,
-    github: bool,
-    tiktok: bool,
-    faceit: bool,
-    dota: bool,
+    #[serde(default)]
+    failure_policy: FailurePolicy,
+    #[serde(default)]
+    card_server: CardServer,
+    services: BTreeMap<String, bool>, // service name -> enabled
 }
 
 #[tokio::main]
@@ -48,6 +61,15 @@ async fn main() {
 
     logs::bot_started();
 
+    if config.card_server.enabled {
+        let server = config.card_server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.serve().await {
+                log::error!("card server stopped: {}", e);
+            }
+        });
+    }
+
     let bot_clone = bot.clone();
     let config_clone = Arc::new(config.clone());
     
@@ -71,8 +93,9 @@ async fn main() {
         .await;
 }
 
-fn load_config() -> Result<GlobalConfig, Box<dyn std::error::Error>> {
-    let config_content = fs::read_to_string("src/configs/global_cfg.yml")?;
+fn load_config() -> Result<GlobalConfig> {
+    let config_content = fs::read_to_string("src/configs/global_cfg.yml")
+        .map_err(|e| TeleStatsError::Config(format!("failed to read src/configs/global_cfg.yml: {}", e)))?;
     let config: GlobalConfig = serde_yaml::from_str(&config_content)?;
     Ok(config)
 }
@@ -88,58 +111,45 @@ async fn update_loop(bot: Bot, config: Arc<GlobalConfig>) {
 }
 
 async fn run_updates(bot: &Bot, config: &GlobalConfig) {
-    if config.services.gitlab {
-        logs::update_started("gitlab");
-        match gitlab::run_gitlab_service(config.channel_id, bot.clone()).await {
-            Ok(_) => logs::update_completed("gitlab"),
-            Err(e) => {
-                if config.ignore_errors {
-                    log::error!("gitlab service update failed: {}", e);
-                } else {
-                    logs::update_failed("gitlab", &e.to_string());
-                }
-            }
-        }
-    }
+    let registry = services::registry();
+    let policy = &config.failure_policy;
 
-    if config.services.github {
-        logs::update_started("github");
-        match github::run_github_service(config.channel_id, bot.clone()).await {
-            Ok(_) => logs::update_completed("github"),
-            Err(e) => {
-                if config.ignore_errors {
-                    log::error!("github service update failed: {}", e);
-                } else {
-                    logs::update_failed("github", &e.to_string());
-                }
-            }
+    for service in &registry {
+        if !config.services.get(service.name()).copied().unwrap_or(false) {
+            continue;
         }
-    }
 
-    if config.services.tiktok {
-        logs::update_started("tiktok");
-        if config.ignore_errors {
-            log::error!("tiktok service update failed: not implemented yet");
-        } else {
-            logs::update_failed("tiktok", "not implemented yet");
+        logs::update_started(service.name());
+        let mut result = service.run(config.channel_id, bot).await;
+
+        let mut attempt = 0;
+        while let Err(e) = &result {
+            if attempt >= policy.retries_for(e) {
+                break;
+            }
+            attempt += 1;
+            log::warn!(
+                "{} service update failed ({}), retry {} in {}s",
+                service.name(), e, attempt, policy.retry_delay
+            );
+            sleep(policy.retry_delay()).await;
+            result = service.run(config.channel_id, bot).await;
         }
-    }
 
-    if config.services.faceit {
-        logs::update_started("faceit");
-        if config.ignore_errors {
-            log::error!("faceit service update failed: not implemented yet");
-        } else {
-            logs::update_failed("faceit", "not implemented yet");
+        match result {
+            Ok(_) => logs::update_completed(service.name()),
+            Err(e) if e.action() == ErrorAction::Skip => {
+                log::warn!("{} service update skipped: {}", service.name(), e)
+            }
+            Err(e) if e.action() == ErrorAction::Abort => policy.abort(bot, service.name(), &e.to_string()).await,
+            Err(e) => policy.handle(bot, service.name(), &e.to_string(), e.action()).await,
         }
     }
 
-    if config.services.dota {
-        logs::update_started("dota");
-        if config.ignore_errors {
-            log::error!("dota service update failed: not implemented yet");
-        } else {
-            logs::update_failed("dota", "not implemented yet");
+    for (name, enabled) in &config.services {
+        if *enabled && !registry.iter().any(|s| s.name() == name) {
+            logs::update_started(name);
+            policy.handle(bot, name, "not implemented yet", ErrorAction::Alert).await;
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,289 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::bitbucket::BitbucketService;
+use crate::card::{self, CardSettings, StatsCard};
+use crate::chart::{self, ChartSettings};
+use crate::combined::CombinedService;
+use crate::dota::DotaService;
+use crate::faceit::FaceitService;
+use crate::gitea::GiteaService;
+use crate::github::GitHubService;
+use crate::gitlab::GitLabService;
+use crate::history::{History, HistorySettings, Snapshot};
+use crate::local::LocalService;
+use crate::state::StateStore;
+use crate::template::MessageFormat;
+use crate::error::{Result, TeleStatsError};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use std::fs;
+use teloxide::prelude::*;
+use teloxide::types::{ChatId, InputFile, InputMedia, InputMediaPhoto, MessageId, ParseMode};
+use teloxide::{ApiError, RequestError};
+
+const CAPTION_LIMIT: usize = 1024; // telegram limit for photo captions
+
+/// A stats provider posted to the channel: loads its own config, collects
+/// stats and renders them into a channel message.
+#[async_trait]
+pub trait StatsService: Send + Sync {
+    type Config: DeserializeOwned + Send + Sync;
+    type Stats: Send + Sync;
+
+    /// Name used in `global_cfg.yml` and in logs.
+    fn name(&self) -> &'static str;
+
+    fn load_config(&self) -> Result<Self::Config> {
+        let config_path = format!("src/configs/{}_cfg.yml", self.name());
+        let config_content = fs::read_to_string(&config_path)
+            .map_err(|e| TeleStatsError::Config(format!("failed to read {}: {}", config_path, e)))?;
+        Ok(serde_yaml::from_str(&config_content)?)
+    }
+
+    /// Channel message to edit, 0 = send one and remember it.
+    fn message_id(&self, config: &Self::Config) -> i64;
+
+    async fn collect_stats(&self, config: &Self::Config) -> Result<Self::Stats>;
+
+    /// History is off unless the service exposes its config block.
+    fn history_settings(&self, _config: &Self::Config) -> HistorySettings {
+        HistorySettings::default()
+    }
+
+    /// Numbers worth keeping for trends, None if the service has none.
+    fn snapshot(&self, _stats: &Self::Stats) -> Option<Snapshot> {
+        None
+    }
+
+    /// Chart posts are off unless the service exposes its config block.
+    fn chart_settings(&self, _config: &Self::Config) -> ChartSettings {
+        ChartSettings::default()
+    }
+
+    /// `(language, share in percent)` pairs to draw, biggest first.
+    fn chart_languages(&self, _stats: &Self::Stats) -> Vec<(String, f64)> {
+        Vec::new()
+    }
+
+    /// Card export is off unless the service exposes its config block.
+    fn card_settings(&self, _config: &Self::Config) -> CardSettings {
+        CardSettings::default()
+    }
+
+    /// Numbers for the svg card, None if the service has no card.
+    fn stats_card(&self, _stats: &Self::Stats, _config: &Self::Config) -> Option<StatsCard> {
+        None
+    }
+
+    /// Markup `render_message` produces.
+    fn message_format(&self, _config: &Self::Config) -> MessageFormat {
+        MessageFormat::MarkdownV2
+    }
+
+    fn render_message(&self, stats: &Self::Stats, config: &Self::Config, context: &RenderContext) -> Result<String>;
+}
+
+/// What the renderer knows besides the fresh stats.
+#[derive(Debug, Default)]
+pub struct RenderContext {
+    /// Earlier snapshot to show deltas against, None = no deltas.
+    pub baseline: Option<Snapshot>,
+    pub period: String, // e.g. "this week"
+    /// `(taken_at, total_lines)` of recent runs for the chart, oldest first.
+    pub series: Vec<(i64, usize)>,
+}
+
+/// A rendered channel post: text alone, or a chart with the text as caption.
+struct Post {
+    text: String,
+    parse_mode: ParseMode,
+    photo: Option<Vec<u8>>, // png
+}
+
+/// Type-erased service the update loop iterates over.
+#[async_trait]
+pub trait ServiceRunner: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn run(&self, channel_id: i64, bot: &Bot) -> Result<()>;
+}
+
+#[async_trait]
+impl<S: StatsService> ServiceRunner for S {
+    fn name(&self) -> &'static str {
+        StatsService::name(self)
+    }
+
+    async fn run(&self, channel_id: i64, bot: &Bot) -> Result<()> {
+        let config = self.load_config()?;
+        let stats = self.collect_stats(&config).await?;
+
+        let name = StatsService::name(self);
+        let chart = self.chart_settings(&config);
+        let series_days = if chart.enabled { chart.history_days } else { 0 };
+        let context = match self.snapshot(&stats) {
+            Some(snapshot) => record_history(name, &self.history_settings(&config), series_days, &snapshot),
+            None => RenderContext::default(),
+        };
+        let message = self.render_message(&stats, &config, &context)?;
+
+        let card_settings = self.card_settings(&config);
+        if card_settings.enabled {
+            if let Some(stats_card) = self.stats_card(&stats, &config) {
+                let svg = card::render_svg(&stats_card, &card_settings);
+                if let Err(e) = card::export(name, svg, &card_settings) {
+                    log::warn!("failed to export {} card: {}", name, e);
+                }
+            }
+        }
+
+        let photo = if chart.enabled {
+            render_chart(name, chart, self.chart_languages(&stats), context.series.clone(), &message).await
+        } else {
+            None
+        };
+        let post = Post {
+            text: message,
+            parse_mode: self.message_format(&config).parse_mode(),
+            photo,
+        };
+
+        publish(bot, name, channel_id, self.message_id(&config), post).await?;
+
+        log::info!("{} stats sent successfully", name);
+
+        Ok(())
+    }
+}
+
+/// Stores the run's snapshot and returns the one to compare it with.
+/// History is best effort: a broken database never fails the update.
+/// `series_days` > 0 also loads recent runs for the chart.
+fn record_history(name: &str, settings: &HistorySettings, series_days: u32, snapshot: &Snapshot) -> RenderContext {
+    if !settings.enabled {
+        return RenderContext::default();
+    }
+
+    let recorded = History::open().and_then(|history| {
+        let baseline = history.baseline(name, settings.compare_days)?;
+        history.record(name, snapshot)?;
+        let series = if series_days > 0 {
+            history.series(name, series_days)?
+        } else {
+            Vec::new()
+        };
+        Ok((baseline, series))
+    });
+
+    match recorded {
+        Ok((baseline, series)) => RenderContext {
+            baseline,
+            period: settings.period_label(),
+            series,
+        },
+        Err(e) => {
+            log::warn!("failed to update {} history: {}", name, e);
+            RenderContext::default()
+        }
+    }
+}
+
+/// Draws the chart for a post. Like history it is best effort: without
+/// it the stats still go out as text.
+async fn render_chart(name: &str, settings: ChartSettings, languages: Vec<(String, f64)>, series: Vec<(i64, usize)>, caption: &str) -> Option<Vec<u8>> {
+    if caption.chars().count() > CAPTION_LIMIT {
+        log::warn!("{} message is too long for a photo caption, posting text only", name);
+        return None;
+    }
+
+    let rendered = tokio::task::spawn_blocking(move || {
+        let svg = chart::stats_chart_svg(settings.kind, &languages, &series);
+        chart::render_png(&svg, &settings)
+    })
+    .await
+    .map_err(TeleStatsError::from)
+    .and_then(|png| png);
+
+    match rendered {
+        Ok(png) => Some(png),
+        Err(e) => {
+            log::warn!("failed to render {} chart: {}", name, e);
+            None
+        }
+    }
+}
+
+/// Edits the service's channel message in place, (re-)sending it when
+/// there is none yet, it was deleted or it switched between text and chart.
+async fn publish(bot: &Bot, name: &str, channel_id: i64, configured: i64, post: Post) -> Result<()> {
+    let mut state = StateStore::load();
+    let chat = ChatId(channel_id);
+
+    match state.message(name, channel_id, configured) {
+        // telegram can't turn a text message into a photo or back
+        Some(posted) if posted.photo != post.photo.is_some() => {
+            log::info!("{} post changed kind, replacing message {}", name, posted.message_id);
+            if let Err(e) = bot.delete_message(chat, MessageId(posted.message_id)).await {
+                log::warn!("failed to delete {} message {}: {}", name, posted.message_id, e);
+            }
+        }
+        Some(posted) => {
+            let message_id = MessageId(posted.message_id);
+            let edited = match &post.photo {
+                Some(png) => {
+                    let media = InputMediaPhoto::new(InputFile::memory(png.clone()))
+                        .caption(post.text.clone())
+                        .parse_mode(post.parse_mode);
+                    bot.edit_message_media(chat, message_id, InputMedia::Photo(media)).await.map(drop)
+                }
+                None => bot.edit_message_text(chat, message_id, post.text.clone())
+                    .parse_mode(post.parse_mode)
+                    .await
+                    .map(drop),
+            };
+
+            match edited {
+                Ok(_) | Err(RequestError::Api(ApiError::MessageNotModified)) => return Ok(()),
+                Err(RequestError::Api(ApiError::MessageToEditNotFound | ApiError::MessageIdInvalid)) => {
+                    log::warn!("{} message {} is gone, sending a new one", name, posted.message_id);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        None => {}
+    }
+
+    let photo = post.photo.is_some();
+    let sent = match post.photo {
+        Some(png) => bot.send_photo(chat, InputFile::memory(png))
+            .caption(post.text)
+            .parse_mode(post.parse_mode)
+            .await?,
+        None => bot.send_message(chat, post.text)
+            .parse_mode(post.parse_mode)
+            .await?,
+    };
+
+    state.set_message(name, channel_id, configured, sent.id.0, photo);
+    if let Err(e) = state.save() {
+        log::warn!("failed to remember {} message id {}: {}", name, sent.id.0, e);
+    }
+
+    Ok(())
+}
+
+/// Every service the bot knows about, in update order. Adding a provider
+/// only means adding it here and enabling it by name in `global_cfg.yml`.
+pub fn registry() -> Vec<Box<dyn ServiceRunner>> {
+    vec![
+        Box::new(GitLabService::new()),
+        Box::new(GitHubService::new()),
+        Box::new(GiteaService::new()),
+        Box::new(BitbucketService::new()),
+        Box::new(LocalService::new()),
+        Box::new(CombinedService), // reads what the forges above stored
+        Box::new(FaceitService),
+        Box::new(DotaService),
+    ]
+}
@@ -0,0 +1,76 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const STATE_PATH: &str = "data/state.json";
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct PostedMessage {
+    pub message_id: i32,
+    /// `message_id` from the service config this one took over from,
+    /// 0 if the config never had one.
+    #[serde(default)]
+    pub replaces: i64,
+    /// Posted as a chart with the stats as caption, not as plain text.
+    #[serde(default)]
+    pub photo: bool,
+}
+
+/// Things the bot learns at runtime and must not forget across restarts.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StateStore {
+    #[serde(default)]
+    messages: HashMap<String, PostedMessage>, // "<service>:<chat id>" -> message
+}
+
+impl StateStore {
+    pub fn load() -> Self {
+        match fs::read_to_string(STATE_PATH) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("ignoring broken state file {}: {}", STATE_PATH, e);
+                StateStore::default()
+            }),
+            Err(_) => StateStore::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = Path::new(STATE_PATH).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(STATE_PATH, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Message to edit: the configured one unless it was deleted and
+    /// re-sent, otherwise whatever the bot posted itself last time.
+    /// A configured id is assumed to be a text message.
+    pub fn message(&self, service: &str, chat_id: i64, configured: i64) -> Option<PostedMessage> {
+        match self.messages.get(&key(service, chat_id)) {
+            Some(posted) if posted.replaces == configured => Some(*posted),
+            _ if configured != 0 => Some(PostedMessage {
+                message_id: configured as i32,
+                replaces: configured,
+                photo: false,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn set_message(&mut self, service: &str, chat_id: i64, configured: i64, message_id: i32, photo: bool) {
+        self.messages.insert(key(service, chat_id), PostedMessage {
+            message_id,
+            replaces: configured,
+            photo,
+        });
+    }
+}
+
+fn key(service: &str, chat_id: i64) -> String {
+    format!("{}:{}", service, chat_id)
+}
@@ -0,0 +1,57 @@
+// MIT License
+// Copyright (c) 2025 shareui
+
+use crate::error::{Result, TeleStatsError};
+use crate::forge::formatter::escape_markdown;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tera::{Context, Tera};
+use teloxide::types::ParseMode;
+
+/// Markup the rendered message is sent with.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    #[default]
+    MarkdownV2,
+    Html,
+}
+
+impl MessageFormat {
+    pub fn parse_mode(self) -> ParseMode {
+        match self {
+            MessageFormat::MarkdownV2 => ParseMode::MarkdownV2,
+            MessageFormat::Html => ParseMode::Html,
+        }
+    }
+}
+
+// telegram only needs these four, tera's own escaper also mangles '/' in links
+fn escape_html(text: &str) -> String {
+    text.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+        .replace("\"", "&quot;")
+}
+
+/// Renders a user template file. Every `{{ value }}` is escaped for the
+/// chosen markup, so templates only write the markup itself; `| safe`
+/// opts out for values that are markup already.
+pub fn render(path: &str, format: MessageFormat, context: &impl Serialize) -> Result<String> {
+    let mut tera = Tera::default();
+    tera.autoescape_on(vec!["message"]); // matched against the name below
+    tera.set_escape_fn(match format {
+        MessageFormat::MarkdownV2 => escape_markdown,
+        MessageFormat::Html => escape_html,
+    });
+
+    // added as a raw template: tera matches autoescape against the file
+    // path when it has one, and user files can be named anything
+    let source = fs::read_to_string(path)
+        .map_err(|e| TeleStatsError::Config(format!("failed to read template {}: {}", path, e)))?;
+    tera.add_raw_template("message", &source)?;
+
+    let context = Context::from_serialize(context)?;
+    Ok(tera.render("message", &context)?)
+}
+